@@ -0,0 +1,12 @@
+use clap::ValueEnum;
+
+/// Represents what is removed from the clean copy of a CSV file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+#[repr(u8)]
+pub enum CleanMode {
+    /// Remove the rows holding a flagged value
+    #[default]
+    Rows,
+    /// Keep every row and blank the flagged cells
+    Cells,
+}
@@ -0,0 +1,154 @@
+use crate::utils::regex::safe_regex::get_email_regex;
+use chrono::{NaiveDate, NaiveDateTime};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::sync::LazyLock;
+
+/// Integer with an optional sign.
+static INTEGER_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^[+-]?\d+$").unwrap());
+
+/// Decimal number with an optional sign, a dot or comma separator and an optional exponent.
+static DECIMAL_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^[+-]?(\d+([.,]\d*)?|[.,]\d+)([eE][+-]?\d+)?$").unwrap());
+
+/// Email address, the same pattern as the built-in allow rule, matching the whole value.
+static EMAIL_REGEX: LazyLock<Regex> = LazyLock::new(get_email_regex);
+
+/// Phone number stripped of its separators, with 6 to 15 digits and an optional international prefix.
+static PHONE_REGEX: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\+?\d{6,15}$").unwrap());
+
+/// Represents the type expected for the values of a column, declared in the schema of the configuration.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ColumnType {
+    /// Integer with an optional sign
+    Integer,
+    /// Decimal number, with a dot or a comma as decimal separator
+    Decimal,
+    /// Date or date and time in the given `chrono` format
+    Date {
+        #[serde(default = "ColumnType::default_date_format")]
+        format: String,
+    },
+    /// Email address
+    Email,
+    /// Phone number, with spaces, dots, dashes and parentheses as separators
+    Phone,
+    /// One of the given values
+    Enum { values: Vec<String> },
+    /// Free text, analysed by the rules and the model
+    Text,
+    /// Column that is not analysed
+    Skip,
+}
+
+impl ColumnType {
+    /// Default format of the dates, such as `2024-02-03`.
+    pub const DEFAULT_DATE_FORMAT: &'static str = "%Y-%m-%d";
+
+    /// Return the default date format, used when the schema does not define one.
+    #[inline]
+    #[must_use]
+    fn default_date_format() -> String {
+        String::from(Self::DEFAULT_DATE_FORMAT)
+    }
+
+    /// Return a string representation of the column type.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Integer => "integer",
+            Self::Decimal => "decimal",
+            Self::Date { .. } => "date",
+            Self::Email => "email",
+            Self::Phone => "phone",
+            Self::Enum { .. } => "enum",
+            Self::Text => "text",
+            Self::Skip => "skip",
+        }
+    }
+
+    /// Return true if the values of the column are checked against the type instead of being sent to the model.
+    #[inline]
+    #[must_use]
+    pub const fn is_checked(&self) -> bool {
+        !matches!(self, Self::Text | Self::Skip)
+    }
+
+    /// Return true if the value is a date or a date and time in the given `chrono` format.
+    #[must_use]
+    pub fn is_date(value: &str, format: &str) -> bool {
+        NaiveDateTime::parse_from_str(value, format).is_ok()
+            || NaiveDate::parse_from_str(value, format).is_ok()
+    }
+
+    /// Return true if the value has the type, free text and skipped columns accepting any value.
+    #[must_use]
+    pub fn is_valid(&self, value: &str) -> bool {
+        match self {
+            Self::Integer => INTEGER_REGEX.is_match(value),
+            Self::Decimal => DECIMAL_REGEX.is_match(value),
+            Self::Date { format } => Self::is_date(value, format),
+            Self::Email => EMAIL_REGEX.is_match(value),
+            Self::Phone => {
+                let digits: String = value
+                    .chars()
+                    .filter(|c| !matches!(c, ' ' | '.' | '-' | '(' | ')'))
+                    .collect();
+                PHONE_REGEX.is_match(&digits)
+            }
+            Self::Enum { values } => values.iter().any(|allowed| allowed == value),
+            Self::Text | Self::Skip => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_column_type_is_valid() {
+        assert!(ColumnType::Integer.is_valid("-42"));
+        assert!(!ColumnType::Integer.is_valid("4.2"));
+        assert!(ColumnType::Decimal.is_valid("3,14"));
+        assert!(!ColumnType::Decimal.is_valid("3.1.4"));
+        assert!(ColumnType::Email.is_valid("user.name+tag@domain.co.uk"));
+        assert!(!ColumnType::Email.is_valid("user@domain.com; DROP TABLE x"));
+        assert!(!ColumnType::Email.is_valid("Contact: user@domain.com"));
+        assert!(ColumnType::Phone.is_valid("+33 (0)6 12-34-56-78"));
+        assert!(!ColumnType::Phone.is_valid("12"));
+        assert!(!ColumnType::Phone.is_valid("06 12 34 56 78 <script>"));
+
+        let date: ColumnType = ColumnType::Date {
+            format: String::from("%d/%m/%Y"),
+        };
+        assert!(date.is_valid("03/02/2024"));
+        assert!(!date.is_valid("2024-02-03"));
+
+        let status: ColumnType = ColumnType::Enum {
+            values: vec![String::from("open"), String::from("closed")],
+        };
+        assert!(status.is_valid("open"));
+        assert!(!status.is_valid("pending"));
+        assert!(ColumnType::Text.is_valid("' OR 1=1 --"));
+    }
+
+    #[tokio::test]
+    async fn test_column_type_deserialize() {
+        let date: ColumnType = serde_json::from_str(r#"{"type": "date"}"#).unwrap();
+        assert_eq!(
+            date,
+            ColumnType::Date {
+                format: String::from(ColumnType::DEFAULT_DATE_FORMAT)
+            }
+        );
+        assert_eq!(
+            serde_json::from_str::<ColumnType>(r#"{"type": "skip"}"#).unwrap(),
+            ColumnType::Skip
+        );
+        assert!(!ColumnType::Skip.is_checked());
+        assert!(ColumnType::Phone.is_checked());
+    }
+}
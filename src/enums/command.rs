@@ -0,0 +1,17 @@
+use crate::structs::cli::{ExplainArgs, ProfileArgs, ScanArgs};
+use clap::Subcommand;
+
+/// Represents the subcommands available in the command line interface.
+#[derive(Subcommand)]
+pub enum Command {
+    /// Scan a CSV file and write an anomaly report
+    Scan(ScanArgs),
+    /// Explain how a single value is classified by the regexes and the model
+    Explain(ExplainArgs),
+    /// Infer the profile of each column of a CSV file and compare it to a saved profile
+    Profile(ProfileArgs),
+    /// Print the resolved configuration and check the referenced files
+    Config,
+    /// Load the model and tokenizer and run them on known values
+    Selftest,
+}
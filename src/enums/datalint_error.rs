@@ -0,0 +1,115 @@
+use std::fmt::Display;
+
+/// Represents the errors returned by the library, so that a failure on one file never stops the whole process.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum DataLintError {
+    Config(String),
+    Io(std::io::Error),
+    Encoding(String),
+    Csv(String),
+    Tokenizer(String),
+    Inference(String),
+}
+
+impl DataLintError {
+    /// Return a short name of the error category.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Config(_) => "configuration",
+            Self::Io(_) => "io",
+            Self::Encoding(_) => "encoding",
+            Self::Csv(_) => "csv",
+            Self::Tokenizer(_) => "tokenizer",
+            Self::Inference(_) => "inference",
+        }
+    }
+}
+
+impl Display for DataLintError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Config(message) => write!(f, "Configuration error: {message}"),
+            Self::Io(e) => write!(f, "I/O error: {e}"),
+            Self::Encoding(message) => write!(f, "Encoding error: {message}"),
+            Self::Csv(message) => write!(f, "CSV error: {message}"),
+            Self::Tokenizer(message) => write!(f, "Tokenizer error: {message}"),
+            Self::Inference(message) => write!(f, "Inference error: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for DataLintError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for DataLintError {
+    /// Converts an I/O error, invalid data being reported as an encoding error.
+    fn from(error: std::io::Error) -> Self {
+        if error.kind() == std::io::ErrorKind::InvalidData {
+            Self::Encoding(error.to_string())
+        } else {
+            Self::Io(error)
+        }
+    }
+}
+
+impl From<csv::Error> for DataLintError {
+    /// Converts a CSV error, keeping the I/O errors of the underlying reader apart.
+    fn from(error: csv::Error) -> Self {
+        if error.is_io_error() {
+            match error.into_kind() {
+                csv::ErrorKind::Io(e) => Self::from(e),
+                kind => Self::Csv(format!("{kind:?}")),
+            }
+        } else {
+            Self::Csv(error.to_string())
+        }
+    }
+}
+
+impl From<tch::TchError> for DataLintError {
+    /// Converts an error raised by libtorch.
+    fn from(error: tch::TchError) -> Self {
+        Self::Inference(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_datalint_error_display() {
+        assert_eq!(
+            DataLintError::Config(String::from("missing field")).to_string(),
+            "Configuration error: missing field"
+        );
+        assert_eq!(
+            DataLintError::Inference(String::from("bad shape")).to_string(),
+            "Inference error: bad shape"
+        );
+        assert_eq!(
+            DataLintError::Tokenizer(String::new()).as_str(),
+            "tokenizer"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_datalint_error_from_io() {
+        let not_found: DataLintError =
+            std::io::Error::new(std::io::ErrorKind::NotFound, "missing").into();
+        assert!(matches!(not_found, DataLintError::Io(_)));
+
+        let invalid: DataLintError =
+            std::io::Error::new(std::io::ErrorKind::InvalidData, "bad bytes").into();
+        assert!(matches!(invalid, DataLintError::Encoding(_)));
+    }
+}
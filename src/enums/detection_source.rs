@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents the detector that reported an anomaly.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum DetectionSource {
+    #[default]
+    Model,
+    Schema,
+    Formula,
+    Xss,
+    Sql,
+    Command,
+    PathTraversal,
+    Ldap,
+    Xpath,
+    Unicode,
+}
+
+impl DetectionSource {
+    /// Every detection source, in the order used to list the rules of a report.
+    pub const ALL: [Self; 10] = [
+        Self::Model,
+        Self::Schema,
+        Self::Formula,
+        Self::Xss,
+        Self::Sql,
+        Self::Command,
+        Self::PathTraversal,
+        Self::Ldap,
+        Self::Xpath,
+        Self::Unicode,
+    ];
+
+    /// Return a string representation of the detection source, used as rule identifier in the reports.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Model => "model",
+            Self::Schema => "schema",
+            Self::Formula => "formula",
+            Self::Xss => "xss",
+            Self::Sql => "sql",
+            Self::Command => "command",
+            Self::PathTraversal => "path_traversal",
+            Self::Ldap => "ldap",
+            Self::Xpath => "xpath",
+            Self::Unicode => "unicode",
+        }
+    }
+
+    /// Return a short description of what the detector reports.
+    #[inline]
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::Model => "Value scored as unsafe by the model above the threshold of its column",
+            Self::Schema => "Value not matching the type declared for its column in the schema",
+            Self::Formula => {
+                "Value run as a formula by spreadsheets, calling a function or another program"
+            }
+            Self::Xss => "HTML or JavaScript payload run when the value is rendered in a web page",
+            Self::Sql => {
+                "SQL structure breaking out of a string literal or adding clauses to the query"
+            }
+            Self::Command => "Shell command chained or substituted into a shell argument",
+            Self::PathTraversal => {
+                "Path climbing out of its directory or truncated by a NUL byte when used as a file name"
+            }
+            Self::Ldap => "Fragment closing an LDAP filter to change the entries it selects",
+            Self::Xpath => "Fragment escaping an XPath predicate to select other nodes",
+            Self::Unicode => {
+                "Characters hiding or disguising the text: bidi controls, invisible or control characters, mixed scripts and look-alike letters"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_detection_source_as_str() {
+        assert_eq!(DetectionSource::Model.as_str(), "model");
+        assert_eq!(DetectionSource::default(), DetectionSource::Model);
+        assert_eq!(
+            serde_json::to_string(&DetectionSource::Model).unwrap(),
+            "\"model\""
+        );
+        assert_eq!(DetectionSource::Schema.as_str(), "schema");
+        assert_eq!(DetectionSource::Formula.as_str(), "formula");
+        assert_eq!(
+            serde_json::to_string(&DetectionSource::Xss).unwrap(),
+            "\"xss\""
+        );
+        assert_eq!(
+            serde_json::to_string(&DetectionSource::PathTraversal).unwrap(),
+            format!("\"{}\"", DetectionSource::PathTraversal.as_str())
+        );
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents a change of the layout or of the content of a CSV file compared to its saved profile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum DriftKind {
+    /// Column of the profile missing from the file
+    MissingColumn,
+    /// Column of the file missing from the profile
+    AddedColumn,
+    /// Column of the profile found under another header at the same position, with the same type
+    RenamedColumn,
+    /// Column whose values no longer have the type of the profile
+    TypeChange,
+    /// Column without empty values in the profile that now has some
+    NullabilityChange,
+    /// Column whose mean, empty values ratio or mean length moved away from the profile
+    DistributionShift,
+}
+
+impl DriftKind {
+    /// Return a string representation of the drift kind.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::MissingColumn => "missing_column",
+            Self::AddedColumn => "added_column",
+            Self::RenamedColumn => "renamed_column",
+            Self::TypeChange => "type_change",
+            Self::NullabilityChange => "nullability_change",
+            Self::DistributionShift => "distribution_shift",
+        }
+    }
+}
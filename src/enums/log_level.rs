@@ -3,6 +3,7 @@
 #[repr(u8)]
 pub enum LogLevel {
     Error,
+    Warning,
     Info,
 }
 
@@ -13,6 +14,7 @@ impl LogLevel {
     pub const fn as_str(&self) -> &'static str {
         match self {
             Self::Error => "ERROR",
+            Self::Warning => "WARNING",
             Self::Info => "INFO",
         }
     }
@@ -25,6 +27,7 @@ mod tests {
     #[tokio::test]
     async fn test_log_level_as_str() {
         assert_eq!(LogLevel::Error.as_str(), "ERROR");
+        assert_eq!(LogLevel::Warning.as_str(), "WARNING");
         assert_eq!(LogLevel::Info.as_str(), "INFO");
     }
 
@@ -1,3 +1,17 @@
+pub mod clean_mode;
 pub mod color;
+pub mod column_type;
+pub mod command;
+pub mod datalint_error;
+pub mod detection_source;
+pub mod drift_kind;
 pub mod log_level;
+pub mod output_format;
+pub mod rule_mode;
+pub mod script;
 pub mod separator;
+pub mod severity;
+pub mod sql_signal;
+pub mod sql_token_kind;
+pub mod structure_issue;
+pub mod unicode_issue;
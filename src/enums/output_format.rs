@@ -0,0 +1,57 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::structs::buffered_report_writer::BufferedReportWriter;
+use crate::structs::csv_report_writer::CsvReportWriter;
+use crate::structs::json_lines_writer::JsonLinesWriter;
+use crate::traits::report_writer::ReportWriter;
+use clap::ValueEnum;
+use std::io::Write;
+
+/// Represents the format used to write the analysis report.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+#[repr(u8)]
+pub enum OutputFormat {
+    Json,
+    Sarif,
+    #[value(name = "jsonl")]
+    JsonLines,
+    Csv,
+}
+
+impl OutputFormat {
+    /// Return the file extension associated with the format.
+    #[inline]
+    #[must_use]
+    pub const fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Sarif => "sarif",
+            Self::JsonLines => "jsonl",
+            Self::Csv => "csv",
+        }
+    }
+
+    /// Return the `ReportWriter` writing a report in this format to the given writer.
+    pub fn report_writer(
+        self,
+        writer: Box<dyn Write>,
+    ) -> Result<Box<dyn ReportWriter>, DataLintError> {
+        Ok(match self {
+            Self::Json | Self::Sarif => Box::new(BufferedReportWriter::new(self, writer)),
+            Self::JsonLines => Box::new(JsonLinesWriter::new(writer)),
+            Self::Csv => Box::new(CsvReportWriter::new(writer)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_output_format_extension() {
+        assert_eq!(OutputFormat::Json.extension(), "json");
+        assert_eq!(OutputFormat::Sarif.extension(), "sarif");
+        assert_eq!(OutputFormat::JsonLines.extension(), "jsonl");
+        assert_eq!(OutputFormat::Csv.extension(), "csv");
+    }
+}
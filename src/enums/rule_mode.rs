@@ -0,0 +1,13 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents how the rules of a rules file are combined with the built-in rules.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum RuleMode {
+    /// Add the rules of the file to the built-in rules
+    #[default]
+    Merge,
+    /// Use the rules of the file instead of the built-in rules
+    Replace,
+}
@@ -0,0 +1,47 @@
+/// Represents the scripts whose letters are told apart by the Unicode detector, as their letters look alike.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Script {
+    Latin,
+    Cyrillic,
+    Greek,
+    Armenian,
+}
+
+impl Script {
+    /// Return the script of a letter, or `None` for the other characters and scripts.
+    #[must_use]
+    pub const fn of(character: char) -> Option<Self> {
+        match character {
+            'A'..='Z'
+            | 'a'..='z'
+            | '\u{00C0}'..='\u{00D6}'
+            | '\u{00D8}'..='\u{00F6}'
+            | '\u{00F8}'..='\u{024F}'
+            | '\u{1E00}'..='\u{1EFF}'
+            | '\u{2C60}'..='\u{2C7F}'
+            | '\u{A720}'..='\u{A7FF}' => Some(Self::Latin),
+            '\u{0400}'..='\u{052F}'
+            | '\u{1C80}'..='\u{1C8F}'
+            | '\u{2DE0}'..='\u{2DFF}'
+            | '\u{A640}'..='\u{A69F}' => Some(Self::Cyrillic),
+            '\u{0370}'..='\u{03FF}' | '\u{1F00}'..='\u{1FFF}' => Some(Self::Greek),
+            '\u{0531}'..='\u{058F}' => Some(Self::Armenian),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_script_of() {
+        assert_eq!(Script::of('é'), Some(Script::Latin));
+        assert_eq!(Script::of('а'), Some(Script::Cyrillic));
+        assert_eq!(Script::of('ο'), Some(Script::Greek));
+        assert_eq!(Script::of('1'), None);
+        assert_eq!(Script::of('×'), None);
+    }
+}
@@ -0,0 +1,52 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents how serious a value matching an unsafe rule is, from the least to the most serious.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum Severity {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl Severity {
+    /// Return a string representation of the severity.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Return the SARIF level of the results of this severity.
+    #[inline]
+    #[must_use]
+    pub const fn sarif_level(&self) -> &'static str {
+        match self {
+            Self::Low => "note",
+            Self::Medium => "warning",
+            Self::High => "error",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_severity() {
+        assert!(Severity::Low < Severity::Medium && Severity::Medium < Severity::High);
+        assert_eq!(Severity::default(), Severity::Medium);
+        assert_eq!(Severity::High.sarif_level(), "error");
+        assert_eq!(
+            serde_json::from_str::<Severity>("\"low\"").unwrap(),
+            Severity::Low
+        );
+    }
+}
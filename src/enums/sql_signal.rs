@@ -0,0 +1,59 @@
+/// Represents a piece of SQL structure found in a value by the SQL injection detector,
+/// each one adding its weight to the score of the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SqlSignal {
+    /// Quote closing the string literal around the value, followed by SQL
+    QuoteBreak,
+    /// Comment hiding the end of the query
+    Comment,
+    /// Condition always true, such as `OR 1=1` or `OR 'a'='a'`
+    Tautology,
+    /// Semicolon followed by another statement, such as `; DROP TABLE users`
+    StackedQuery,
+    /// `UNION SELECT` adding rows from another query
+    UnionSelect,
+    /// SQL keyword, a weak signal as many words of business text are SQL keywords
+    Keyword,
+}
+
+impl SqlSignal {
+    /// Return a string representation of the SQL signal.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::QuoteBreak => "quote_break",
+            Self::Comment => "comment",
+            Self::Tautology => "tautology",
+            Self::StackedQuery => "stacked_query",
+            Self::UnionSelect => "union_select",
+            Self::Keyword => "keyword",
+        }
+    }
+
+    /// Return the weight of the signal in the score of a value, the signals seen in business text weighing the least.
+    #[inline]
+    #[must_use]
+    pub const fn weight(&self) -> f64 {
+        match self {
+            Self::StackedQuery | Self::UnionSelect => 0.7,
+            Self::Tautology => 0.6,
+            Self::QuoteBreak => 0.4,
+            Self::Comment => 0.3,
+            Self::Keyword => 0.1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sql_signal() {
+        assert_eq!(SqlSignal::UnionSelect.as_str(), "union_select");
+        // A keyword alone or with a comment is never reported
+        assert!(SqlSignal::Keyword.weight() + SqlSignal::Comment.weight() < 0.5);
+    }
+}
@@ -0,0 +1,23 @@
+/// Represents the kind of a token of a value read as SQL by the SQL injection detector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SqlTokenKind {
+    /// Keyword or identifier
+    Word,
+    /// Number, including its decimals
+    Number,
+    /// String literal closed by its quote
+    String,
+    /// String literal still open at the end of the value
+    UnterminatedString,
+    /// Comparison or logical operator, such as `=`, `<>` or `||`
+    Operator,
+    /// Comment running to the end of the value, or between `/*` and `*/`
+    Comment,
+    /// Semicolon ending a statement
+    Semicolon,
+    /// Opening or closing parenthesis
+    Parenthesis,
+    /// Any other character
+    Other,
+}
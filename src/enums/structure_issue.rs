@@ -0,0 +1,111 @@
+use crate::enums::severity::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Represents a structural problem of a CSV file, found in its layout rather than in its values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[repr(u8)]
+pub enum StructureIssue {
+    /// Row whose number of fields differs from the number of headers
+    RaggedRow,
+    /// Row or header ending with a delimiter, which adds an empty field
+    TrailingDelimiter,
+    /// Quote inside an unquoted field, after a closing quote, or never closed
+    UnbalancedQuote,
+    /// NUL character inside a field, which truncates the value in many readers
+    EmbeddedNul,
+    /// Byte order mark inside a header name, left by the concatenation of files
+    BomInHeader,
+    /// Header name given to several columns
+    DuplicateHeader,
+    /// Empty line between the records
+    BlankLine,
+}
+
+impl StructureIssue {
+    /// Every structural issue, in the order used to list the rules of a report.
+    pub const ALL: [Self; 7] = [
+        Self::RaggedRow,
+        Self::TrailingDelimiter,
+        Self::UnbalancedQuote,
+        Self::EmbeddedNul,
+        Self::BomInHeader,
+        Self::DuplicateHeader,
+        Self::BlankLine,
+    ];
+
+    /// Return a string representation of the structural issue.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::RaggedRow => "ragged_row",
+            Self::TrailingDelimiter => "trailing_delimiter",
+            Self::UnbalancedQuote => "unbalanced_quote",
+            Self::EmbeddedNul => "embedded_nul",
+            Self::BomInHeader => "bom_in_header",
+            Self::DuplicateHeader => "duplicate_header",
+            Self::BlankLine => "blank_line",
+        }
+    }
+
+    /// Return the identifier of the structural issue as a rule of the reports, kept apart from the detection sources.
+    #[inline]
+    #[must_use]
+    pub const fn rule_id(&self) -> &'static str {
+        match self {
+            Self::RaggedRow => "structure/ragged_row",
+            Self::TrailingDelimiter => "structure/trailing_delimiter",
+            Self::UnbalancedQuote => "structure/unbalanced_quote",
+            Self::EmbeddedNul => "structure/embedded_nul",
+            Self::BomInHeader => "structure/bom_in_header",
+            Self::DuplicateHeader => "structure/duplicate_header",
+            Self::BlankLine => "structure/blank_line",
+        }
+    }
+
+    /// Return a short description of the structural issue.
+    #[inline]
+    #[must_use]
+    pub const fn description(&self) -> &'static str {
+        match self {
+            Self::RaggedRow => "Row whose number of fields differs from the number of headers",
+            Self::TrailingDelimiter => "Row or header ending with a delimiter",
+            Self::UnbalancedQuote => {
+                "Quote inside an unquoted field, after a closing quote, or never closed"
+            }
+            Self::EmbeddedNul => "NUL character inside a field",
+            Self::BomInHeader => "Byte order mark inside a header name",
+            Self::DuplicateHeader => "Header name given to several columns",
+            Self::BlankLine => "Empty line between the records",
+        }
+    }
+
+    /// Return how serious the structural issue is: the ones that shift or truncate the values are the most serious.
+    #[inline]
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::UnbalancedQuote | Self::EmbeddedNul => Severity::High,
+            Self::RaggedRow | Self::BomInHeader | Self::DuplicateHeader => Severity::Medium,
+            Self::TrailingDelimiter | Self::BlankLine => Severity::Low,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_structure_issue() {
+        for issue in StructureIssue::ALL {
+            assert_eq!(issue.rule_id(), format!("structure/{}", issue.as_str()));
+        }
+        assert_eq!(
+            serde_json::to_string(&StructureIssue::RaggedRow).unwrap(),
+            "\"ragged_row\""
+        );
+        assert_eq!(StructureIssue::EmbeddedNul.severity(), Severity::High);
+    }
+}
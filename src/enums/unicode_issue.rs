@@ -0,0 +1,42 @@
+use crate::enums::severity::Severity;
+
+/// Represents a kind of character hiding or disguising the text of a value, found by the Unicode detector.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum UnicodeIssue {
+    /// Bidirectional control reordering the text, such as the right-to-left override U+202E
+    Bidi,
+    /// Character without glyph inside Latin text, such as the zero-width space U+200B
+    Invisible,
+    /// C0 or C1 control character other than a tab or a line break
+    Control,
+    /// Word mixing the letters of several scripts
+    MixedScript,
+    /// Cyrillic or Greek letter looking like a Latin letter, inside Latin text or an address
+    Confusable,
+}
+
+impl UnicodeIssue {
+    /// Return a string representation of the Unicode issue.
+    #[inline]
+    #[must_use]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bidi => "bidi",
+            Self::Invisible => "invisible",
+            Self::Control => "control",
+            Self::MixedScript => "mixed_script",
+            Self::Confusable => "confusable",
+        }
+    }
+
+    /// Return how serious the Unicode issue is: the ones changing what a reader sees are the most serious.
+    #[inline]
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        match self {
+            Self::Bidi | Self::Control | Self::Confusable => Severity::High,
+            Self::Invisible | Self::MixedScript => Severity::Medium,
+        }
+    }
+}
@@ -4,76 +4,597 @@ mod benches;
 #[cfg(test)]
 mod tests;
 
+use clap::Parser;
+use datalib::enums::color::Color;
+use datalib::enums::column_type::ColumnType;
+use datalib::enums::command::Command;
 use datalib::enums::log_level::LogLevel;
-use datalib::structs::anomaly::Anomaly;
+use datalib::structs::batch_summary::{BatchSummary, FileSummary};
+use datalib::structs::cli::{Cli, ExplainArgs, ProfileArgs, ScanArgs};
+use datalib::structs::column_stats::ColumnStats;
 use datalib::structs::csv_file::CsvFile;
-use datalib::structs::logger::log_and_print_message;
+use datalib::structs::csv_splitter::CsvSplitter;
+use datalib::structs::file_profile::FileProfile;
+use datalib::structs::loaded_model::LoadedModel;
+use datalib::structs::logger::{log_and_print_message, print_message, set_quiet};
 use datalib::structs::model::Model;
+use datalib::structs::report_summary::ReportSummary;
+use datalib::structs::rule::Rule;
+use datalib::structs::rule_matches::RuleMatches;
+use datalib::structs::rule_set::RuleSet;
+use datalib::structs::schema_drift::SchemaDrift;
+use datalib::structs::structure_finding::StructureFinding;
+use datalib::traits::report_writer::ReportWriter;
+use datalib::utils::batch::{find_csv_files, is_batch_input, scan_files};
 use datalib::utils::util::{
-    file_exists, generate_json_file, get_file_from_args, print_report, run_post_execution,
+    REPORT_DIR, STD_STREAM, create_output_file, create_report_writer, default_output_path,
+    finish_report, is_same_file, is_std_stream, print_report, resolve_config_path,
 };
+use std::error::Error;
+use std::fs::File;
+use std::io::Write;
+use std::num::NonZeroUsize;
+use std::path::{Path, PathBuf};
 use std::process::exit;
 use std::time::Instant;
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    let args: [String; 2] =
-        get_file_from_args(&args).expect("Error parsing command line arguments. Usage: datalib <csv_file> <output_file>");
+    let cli: Cli = Cli::parse();
+    set_quiet(cli.quiet);
 
+    let result: Result<(), Box<dyn Error>> = match &cli.command {
+        Command::Scan(args) => run_scan(&cli, args),
+        Command::Explain(args) => run_explain(&cli, args),
+        Command::Profile(args) => run_profile(args),
+        Command::Config => run_config(&cli),
+        Command::Selftest => run_selftest(&cli),
+    };
 
+    if let Err(e) = result {
+        log_and_print_message(&format!("{e}"), &LogLevel::Error);
+        exit(1);
+    }
+}
+
+/// Load the model configuration, apply the command line overrides and check the referenced files.
+fn load_model(cli: &Cli) -> Result<Model, Box<dyn Error>> {
+    let model: Model = load_config(cli)?;
+    check_model_files(&model)?;
+    Ok(model)
+}
+
+/// Load the configuration with the command line overrides, checking that its rules file exists,
+/// for the commands that do not always need the model.
+fn load_config(cli: &Cli) -> Result<Model, Box<dyn Error>> {
+    let config_path: PathBuf = resolve_config_path(cli.config.as_deref());
+
+    let mut model: Model = Model::from_config_file(&config_path).map_err(|e| {
+        format!(
+            "Error loading model configuration from {}: {e}",
+            config_path.display()
+        )
+    })?;
+    apply_cli_overrides(cli, &mut model);
+
+    if let Some(rules_path) = &model.rules_path {
+        check_referenced_file(rules_path)?;
+    }
+    Ok(model)
+}
+
+/// Check that the model and vocabulary files referenced by the configuration exist.
+fn check_model_files(model: &Model) -> Result<(), Box<dyn Error>> {
+    check_referenced_file(&model.model_path)?;
+    check_referenced_file(&model.vocabulary_path)
+}
+
+/// Check that a file referenced by the configuration exists.
+fn check_referenced_file(path: &str) -> Result<(), Box<dyn Error>> {
+    if Path::new(path).exists() {
+        Ok(())
+    } else {
+        Err(format!("File {path} referenced by the configuration does not exist").into())
+    }
+}
+
+/// Override the configured thresholds and rules file with the ones given on the command line.
+fn apply_cli_overrides(cli: &Cli, model: &mut Model) {
+    if let Some(threshold) = cli.threshold {
+        model.threshold = threshold;
+    }
+
+    if let Some(rules_path) = &cli.rules {
+        model.rules_path = Some(rules_path.to_string_lossy().into_owned());
+    }
+
+    model
+        .column_thresholds
+        .extend(cli.column_thresholds.iter().cloned());
+}
+
+/// Scan a CSV file and write the report.
+/// `-` reads the CSV file from the standard input or writes the report to the standard output.
+fn run_scan(cli: &Cli, args: &ScanArgs) -> Result<(), Box<dyn Error>> {
     let start_time: Instant = Instant::now();
 
-    let perfage_iae: Model =
-        Model::from_config_file("config.json").unwrap_or_else(|e| {
-            log_and_print_message(
-                &format!("Error loading model configuration: {e}"),
-                &LogLevel::Error,
-            );
-            exit(1);
-        });
+    let input_path: String = args.input.to_string_lossy().replace('\\', "/");
+    if is_batch_input(&input_path) {
+        return run_batch_scan(cli, args, &input_path);
+    }
 
-    [&perfage_iae.model_path, &perfage_iae.vocabulary_path]
-        .iter()
-        .for_each(|path| {
-            if !file_exists(path) {
-                exit(1);
-            }
-        });
+    let output_path: PathBuf = args.output.clone().unwrap_or_else(|| {
+        if is_std_stream(&input_path) {
+            PathBuf::from(STD_STREAM)
+        } else {
+            default_output_path(&input_path, args.format.extension())
+        }
+    });
 
-    let csv_struct: CsvFile = CsvFile::from_file(&args[0]).unwrap_or_else(|e| {
-        log_and_print_message(
-            &format!("Error reading CSV file: {e}"),
-            &LogLevel::Error,
+    // The standard output is reserved for the report
+    let report_to_stdout: bool = output_path == Path::new(STD_STREAM);
+    if report_to_stdout {
+        set_quiet(true);
+    }
+
+    let model: Model = load_model(cli)?;
+
+    if !is_std_stream(&input_path) && !Path::new(&input_path).exists() {
+        return Err(format!("File {input_path} does not exist").into());
+    }
+
+    let csv_struct: CsvFile = CsvFile::from_file_with_separator(&input_path, args.delimiter)
+        .map_err(|e| format!("Error reading CSV file: {e}"))?;
+
+    if cli.verbose > 0 {
+        print_message(
+            &format!(
+                "Encoding: {}, separator: {:?} (confidence {:.2})",
+                csv_struct.encoding.name(),
+                csv_struct.separator as char,
+                csv_struct.separator_confidence
+            ),
+            &LogLevel::Info,
         );
-        exit(1);
-    });
+    }
 
-    let (dangerous_output, ai_analyze, regex_analyze): (Vec<Anomaly>, u32, u32) =
-        perfage_iae.analyse_file(&csv_struct).unwrap_or_else(|e| {
-            log_and_print_message(
-                &format!("Error analyzing file: {e}"),
-                &LogLevel::Error,
-            );
-            exit(1);
-        });
+    for path in [
+        Some(&output_path),
+        args.clean.as_ref(),
+        args.quarantine.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if is_same_file(Path::new(&input_path), path) {
+            return Err(format!("{} would overwrite the analysed file", path.display()).into());
+        }
+    }
+
+    let split_outputs: bool = args.clean.is_some() || args.quarantine.is_some();
+    if split_outputs && csv_struct.is_stdin() {
+        return Err(
+            "--clean and --quarantine read the CSV file again and need a file input".into(),
+        );
+    }
+    if args.baseline.is_some() && csv_struct.is_stdin() {
+        return Err("--baseline reads the CSV file again and needs a file input".into());
+    }
+
+    let mut splitter: CsvSplitter = CsvSplitter::new();
+    let print_anomalies: bool = cli.verbose > 0 && !report_to_stdout;
+    let mut report_writer: Box<dyn ReportWriter> = create_report_writer(&output_path, args.format)
+        .map_err(|e| format!("Error while creating the report: {e}"))?;
+    let mut anomalies_count: usize = 0;
+
+    let (ai_analyze, regex_analyze, column_stats, structure_findings): (
+        u32,
+        u32,
+        Vec<ColumnStats>,
+        Vec<StructureFinding>,
+    ) = model
+        .analyse_file_streaming(&csv_struct, |anomaly| {
+            anomalies_count += 1;
+            if print_anomalies {
+                println!("{}", anomaly.as_str());
+            }
+            if split_outputs {
+                splitter.add_anomaly(&anomaly);
+            }
+            report_writer.write_anomaly(anomaly)
+        })
+        .map_err(|e| format!("Error analyzing file: {e}"))?;
 
+    let findings_count: usize = structure_findings.len();
+    if findings_count > 0 {
+        log_and_print_message(
+            &format!(
+                "{findings_count} structural problems found in {}",
+                csv_struct.csv_file_path
+            ),
+            &LogLevel::Warning,
+        );
+    }
+    for finding in structure_findings {
+        if print_anomalies {
+            println!("{}", finding.as_str());
+        }
+        report_writer
+            .write_finding(finding)
+            .map_err(|e| format!("Error while saving the report: {e}"))?;
+    }
 
     print_report(
         &start_time,
-        &dangerous_output,
+        anomalies_count,
         &csv_struct.csv_file_path,
-        cfg!(debug_assertions),
+        print_anomalies,
     );
 
-    generate_json_file(
-        dangerous_output,
-        regex_analyze,
+    let summary: ReportSummary = ReportSummary::new(
+        &csv_struct,
+        anomalies_count,
+        findings_count,
         ai_analyze,
-        &csv_struct.csv_file_path,
-        &args[1],
+        regex_analyze,
         start_time.elapsed().as_millis(),
+        column_stats,
+    );
+    finish_report(report_writer.as_mut(), &summary, &output_path)
+        .map_err(|e| format!("Error while saving the report: {e}"))?;
+
+    if split_outputs {
+        write_split_outputs(args, &csv_struct, &splitter)
+            .map_err(|e| format!("Error while writing the clean and quarantine files: {e}"))?;
+    }
+
+    if let Some(baseline) = &args.baseline {
+        let profile: FileProfile = FileProfile::from_csv_file(&csv_struct)
+            .map_err(|e| format!("Error profiling file: {e}"))?;
+        check_drift(&profile, baseline)?;
+    }
+
+    Ok(())
+}
+
+/// Write the clean copy and the quarantine file of the CSV file requested on the command line.
+fn write_split_outputs(
+    args: &ScanArgs,
+    csv_struct: &CsvFile,
+    splitter: &CsvSplitter,
+) -> Result<(), Box<dyn Error>> {
+    let mut clean_file: Option<File> = args.clean.as_deref().map(create_output_file).transpose()?;
+    let mut quarantine_file: Option<File> = args
+        .quarantine
+        .as_deref()
+        .map(create_output_file)
+        .transpose()?;
+
+    let (clean_rows, quarantined_rows): (usize, usize) = splitter.split(
+        csv_struct,
+        clean_file.as_mut().map(|file| file as &mut dyn Write),
+        args.clean_mode,
+        quarantine_file.as_mut().map(|file| file as &mut dyn Write),
+    )?;
+
+    if let Some(clean) = &args.clean {
+        print_message(
+            &format!(
+                "{clean_rows} rows written to the clean file {}",
+                clean.display()
+            ),
+            &LogLevel::Info,
+        );
+    }
+    if let Some(quarantine) = &args.quarantine {
+        print_message(
+            &format!(
+                "{quarantined_rows} rows written to the quarantine file {}",
+                quarantine.display()
+            ),
+            &LogLevel::Info,
+        );
+    }
+    Ok(())
+}
+
+/// Scan every CSV file of a directory or matching a glob pattern with the same loaded model,
+/// writing one report per file and an aggregate summary in the output directory.
+fn run_batch_scan(cli: &Cli, args: &ScanArgs, input_path: &str) -> Result<(), Box<dyn Error>> {
+    const SUMMARY_FILE: &str = "datalint-summary.json";
+    let start_time: Instant = Instant::now();
+
+    let output_dir: PathBuf = args
+        .output
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(REPORT_DIR));
+    if args.clean.is_some() || args.quarantine.is_some() {
+        return Err("--clean and --quarantine only apply to a single file".into());
+    }
+    if args.baseline.is_some() {
+        return Err("--baseline only applies to a single file".into());
+    }
+    if output_dir == Path::new(STD_STREAM) {
+        return Err("The reports of several files cannot be written to the standard output".into());
+    }
+
+    let (base_dir, files): (PathBuf, Vec<PathBuf>) = find_csv_files(input_path)?;
+    if files.is_empty() {
+        return Err(format!("No CSV file found for {input_path}").into());
+    }
+
+    let loaded_model: LoadedModel = load_model(cli)?.load()?;
+    let file_summaries: Vec<FileSummary> = scan_files(
+        &loaded_model,
+        &files,
+        &base_dir,
+        &output_dir,
+        args.format,
+        args.delimiter,
+        args.jobs.map_or(0, NonZeroUsize::get),
+    )?;
+
+    let summary: BatchSummary = BatchSummary::new(file_summaries, start_time.elapsed().as_millis());
+    std::fs::create_dir_all(&output_dir)?;
+    let summary_path: PathBuf = output_dir.join(SUMMARY_FILE);
+    summary.save_to_file(&summary_path)?;
+
+    log_and_print_message(
+        &format!(
+            "Analysed {} files, {} anomalies found, {} failed, summary created at {}",
+            summary.analysed_files,
+            summary.anomalies,
+            summary.failed_files,
+            summary_path.display()
+        ),
+        &LogLevel::Info,
+    );
+
+    if summary.failed_files > 0 {
+        return Err(format!("{} files could not be analysed", summary.failed_files).into());
+    }
+    Ok(())
+}
+
+/// Infer the profile of each column of a CSV file, write it and compare it to the baseline profile if given.
+fn run_profile(args: &ProfileArgs) -> Result<(), Box<dyn Error>> {
+    let input_path: String = args.input.to_string_lossy().replace('\\', "/");
+    let output_path: PathBuf = args.output.clone().unwrap_or_else(|| {
+        if is_std_stream(&input_path) {
+            PathBuf::from(STD_STREAM)
+        } else {
+            default_output_path(&input_path, "profile.json")
+        }
+    });
+
+    // The standard output is reserved for the profile
+    let profile_to_stdout: bool = output_path == Path::new(STD_STREAM);
+    if profile_to_stdout {
+        set_quiet(true);
+    }
+
+    if !is_std_stream(&input_path) && !Path::new(&input_path).exists() {
+        return Err(format!("File {input_path} does not exist").into());
+    }
+    if is_same_file(Path::new(&input_path), &output_path) {
+        return Err(format!(
+            "{} would overwrite the analysed file",
+            output_path.display()
+        )
+        .into());
+    }
+
+    let csv_struct: CsvFile = CsvFile::from_file_with_separator(&input_path, args.delimiter)
+        .map_err(|e| format!("Error reading CSV file: {e}"))?;
+    let profile: FileProfile = FileProfile::from_csv_file(&csv_struct)
+        .map_err(|e| format!("Error profiling file: {e}"))?;
+
+    if profile_to_stdout {
+        profile.write_to(std::io::stdout().lock())?;
+    } else {
+        profile
+            .write_to(create_output_file(&output_path)?)
+            .map_err(|e| format!("Error while saving the profile: {e}"))?;
+        log_and_print_message(
+            &format!(
+                "Profile of {} columns created at {}",
+                profile.columns.len(),
+                output_path.display()
+            ),
+            &LogLevel::Info,
+        );
+    }
+
+    match &args.baseline {
+        Some(baseline) => check_drift(&profile, baseline),
+        None => Ok(()),
+    }
+}
+
+/// Compare the profile of a file to a saved profile, printing each drift as a warning.
+/// Returns an error when the layout of the file has drifted, so that the exit code reports it.
+fn check_drift(profile: &FileProfile, baseline_path: &Path) -> Result<(), Box<dyn Error>> {
+    let baseline: FileProfile = FileProfile::from_file(baseline_path)?;
+    let drifts: Vec<SchemaDrift> = profile.compare(&baseline);
+
+    for drift in &drifts {
+        log_and_print_message(&drift.as_str(), &LogLevel::Warning);
+    }
+
+    if drifts.is_empty() {
+        print_message(
+            &format!("No schema drift from {}", baseline_path.display()),
+            &LogLevel::Info,
+        );
+        Ok(())
+    } else {
+        Err(format!(
+            "{} schema drifts from {}",
+            drifts.len(),
+            baseline_path.display()
+        )
+        .into())
+    }
+}
+
+/// Print how a single value goes through the regex filters and the model.
+fn run_explain(cli: &Cli, args: &ExplainArgs) -> Result<(), Box<dyn Error>> {
+    let value: &str = args.value.trim();
+    let column_name: &str = args.column.as_deref().unwrap_or_default();
+    // The model is only needed, and its files only checked, when the value is sent to it
+    let config: Model = load_config(cli)?;
+    let rule_set: RuleSet = config.rule_set()?;
+
+    println!("Value: {}{value}{}", Color::Red, Color::Reset);
+
+    // Detected values are anomalies without going through the rules and the model
+    if !value.is_empty()
+        && let Some(detection) = rule_set.detect_in_column(&args.value, column_name)
+    {
+        println!(
+            "Detected as: {} ({})",
+            detection.source.as_str(),
+            detection.severity.as_str()
+        );
+        println!("Detection rules: {}", detection.reasons.join(", "));
+        println!(
+            "Score: {}{}{}",
+            Color::Yellow,
+            detection.score,
+            Color::Reset
+        );
+        println!("Reported by the detector from: {}", detection.threshold);
+        println!("Sent to the model: false");
+        println!("Anomaly: true");
+        return Ok(());
+    }
+
+    // Typed and skipped columns do not go through the rules and the model, the header taking precedence over the index
+    let column_type: Option<&ColumnType> = match args.column_index {
+        Some(column_index) => config.schema.column_type(column_index, column_name),
+        None => config.schema.columns.get(column_name),
+    };
+    if let Some(column_type) = column_type
+        && *column_type != ColumnType::Text
+    {
+        println!("Column type: {}", column_type.as_str());
+        println!(
+            "Matches the column type: {}",
+            value.is_empty() || column_type.is_valid(value)
+        );
+        println!("Sent to the model: false");
+        return Ok(());
+    }
+
+    let rule_matches: RuleMatches = rule_set.matches(value);
+    let allowed_by: Vec<&Rule> = rule_set.allowed_by(&rule_matches, column_name);
+    let denied_by: Vec<&Rule> = rule_set.denied_by(&rule_matches, column_name);
+    let is_safe: bool = value.is_empty() || !allowed_by.is_empty();
+
+    println!("Matches a safe pattern: {is_safe}");
+    if !allowed_by.is_empty() {
+        let names: Vec<&str> = allowed_by.iter().map(|rule| rule.name.as_str()).collect();
+        println!("Safe patterns: {}", names.join(", "));
+    }
+    println!("Matches an unsafe pattern: {}", rule_matches.is_denied());
+    if !denied_by.is_empty() {
+        println!("Unsafe patterns: {}", denied_rule_names(&denied_by));
+    }
+
+    if is_safe || denied_by.is_empty() {
+        println!("Sent to the model: false");
+        return Ok(());
+    }
+
+    check_model_files(&config)?;
+    let model: LoadedModel = config.load()?;
+    let (score, top_tokens): (f64, Vec<String>) = model.explain_value(value)?;
+
+    let threshold: f64 = args
+        .column
+        .as_deref()
+        .map_or(model.config.threshold, |column| {
+            model.config.threshold_for(column)
+        });
+
+    println!("Sent to the model: true");
+    println!("Score: {}{score}{}", Color::Yellow, Color::Reset);
+    println!("Threshold: {threshold}");
+    if !top_tokens.is_empty() {
+        println!("Top tokens: {}", top_tokens.join(", "));
+    }
+    println!("Anomaly: {}", score > threshold);
+    Ok(())
+}
+
+/// Return the names of the deny rules with their severity, separated by commas.
+fn denied_rule_names(rules: &[&Rule]) -> String {
+    rules
+        .iter()
+        .map(|rule| format!("{} ({})", rule.name, rule.severity.as_str()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Print the resolved configuration.
+fn run_config(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    let config_path: PathBuf = resolve_config_path(cli.config.as_deref());
+    let mut model: Model = Model::from_config_file(&config_path)?;
+    apply_cli_overrides(cli, &mut model);
+
+    println!("Configuration file: {}", config_path.display());
+    println!("{}", serde_json::to_string_pretty(&model)?);
+
+    for path in [&model.model_path, &model.vocabulary_path]
+        .into_iter()
+        .chain(model.rules_path.as_ref())
+    {
+        println!(
+            "{path}: {}",
+            if std::path::Path::new(path).exists() {
+                "found"
+            } else {
+                "missing"
+            }
+        );
+    }
+
+    match model.rule_set() {
+        Ok(rule_set) => println!(
+            "Rules: {} allow, {} deny",
+            rule_set.allow_rules().len(),
+            rule_set.deny_rules().len()
+        ),
+        Err(e) => println!("Rules: {e}"),
+    }
+    Ok(())
+}
+
+/// Check that the model and tokenizer load and give coherent scores on known values.
+fn run_selftest(cli: &Cli) -> Result<(), Box<dyn Error>> {
+    const SAFE_VALUE: &str = "John Smith";
+    const UNSAFE_VALUE: &str = "'; DROP TABLE users; --";
+
+    let model: Model = load_model(cli)?;
+    let scores: Vec<f64> =
+        model.score_values(&[String::from(SAFE_VALUE), String::from(UNSAFE_VALUE)])?;
+
+    let [safe_score, unsafe_score] = scores[..] else {
+        return Err(format!("Expected 2 scores from the model, got {}", scores.len()).into());
+    };
+
+    print_message(
+        &format!("Score of {SAFE_VALUE:?}: {safe_score}"),
+        &LogLevel::Info,
+    );
+    print_message(
+        &format!("Score of {UNSAFE_VALUE:?}: {unsafe_score}"),
+        &LogLevel::Info,
     );
 
-    // Clean-up
-    run_post_execution(&csv_struct.csv_file_path);
+    if safe_score >= unsafe_score {
+        return Err("Self-test failed: the unsafe value is not scored above the safe value".into());
+    }
+
+    print_message("Self-test passed", &LogLevel::Info);
+    Ok(())
 }
@@ -1,26 +1,49 @@
 use crate::enums::color::Color;
+use crate::enums::detection_source::DetectionSource;
+use crate::enums::severity::Severity;
+use crate::structs::field_region::FieldRegion;
 use serde::{Deserialize, Serialize};
 
-/// Represents an anomaly detected in a CSV file.
+/// Represents an anomaly detected in a CSV file, with the detector that reported it, the reasons of the detection
+/// and the highest severity of the unsafe rules matching its value.
+/// The column index starts at 0 and the line of the header is 1, the line being where the record of the value starts.
+/// The region locates the field of the value when the file is scanned, a quoted field possibly spanning lines.
 #[derive(Serialize, Deserialize, Clone)]
 #[non_exhaustive]
 pub struct Anomaly {
     pub value: String,
     pub column: String,
+    #[serde(default)]
+    pub column_index: usize,
     pub score: f32,
+    pub threshold: f32,
     pub line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<FieldRegion>,
+    #[serde(default)]
+    pub source: DetectionSource,
+    #[serde(default)]
+    pub reasons: Vec<String>,
+    #[serde(default)]
+    pub severity: Severity,
 }
 
 impl Anomaly {
     /// Create a new instance of Anomaly
     #[inline]
     #[must_use]
-    pub const fn new(value: String, column: String, line: u32, score: f32) -> Self {
+    pub const fn new(value: String, column: String, line: u32, score: f32, threshold: f32) -> Self {
         Self {
             value,
             column,
+            column_index: 0,
             score,
+            threshold,
             line,
+            region: None,
+            source: DetectionSource::Model,
+            reasons: Vec::new(),
+            severity: Severity::Medium,
         }
     }
 
@@ -31,12 +54,22 @@ impl Anomaly {
         }
     }
 
+    /// Return the reasons of the anomaly separated by commas, or its detection source if it has no reason.
+    #[must_use]
+    pub fn reason(&self) -> String {
+        if self.reasons.is_empty() {
+            String::from(self.source.as_str())
+        } else {
+            self.reasons.join(", ")
+        }
+    }
+
     /// Return a formatted string representation of the anomaly
     #[inline]
     #[must_use]
     pub fn as_str(&self) -> String {
         format!(
-            "Content: {}{}{}, \nColumn: {}{}{}, \nLine: {}{}{}, \nScore: {}{}{}\n-----",
+            "Content: {}{}{}, \nColumn: {}{}{}, \nLine: {}{}{}, \nScore: {}{}{} (threshold {}), \nReasons: {}\n-----",
             Color::Red,
             self.value,
             Color::Reset,
@@ -48,7 +81,9 @@ impl Anomaly {
             Color::Reset,
             Color::Yellow,
             self.score,
-            Color::Reset
+            Color::Reset,
+            self.threshold,
+            self.reason()
         )
     }
 }
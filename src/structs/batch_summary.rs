@@ -0,0 +1,103 @@
+use crate::enums::datalint_error::DataLintError;
+use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Represents the result of the analysis of one file of a batch scan, with the path of its report
+/// or the error that stopped its analysis.
+#[derive(Serialize, Deserialize, Clone)]
+#[non_exhaustive]
+pub struct FileSummary {
+    pub analysed_file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub report: Option<String>,
+    pub anomalies: usize,
+    pub ai_analyze: u32,
+    pub regex_analyze: u32,
+    pub time_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl FileSummary {
+    /// Create the summary of a file analysed successfully.
+    #[inline]
+    #[must_use]
+    pub const fn new(
+        analysed_file: String,
+        report: String,
+        anomalies: usize,
+        ai_analyze: u32,
+        regex_analyze: u32,
+        time_ms: u128,
+    ) -> Self {
+        Self {
+            analysed_file,
+            report: Some(report),
+            anomalies,
+            ai_analyze,
+            regex_analyze,
+            time_ms,
+            error: None,
+        }
+    }
+
+    /// Create the summary of a file whose analysis failed.
+    #[inline]
+    #[must_use]
+    pub const fn failed(analysed_file: String, error: String, time_ms: u128) -> Self {
+        Self {
+            analysed_file,
+            report: None,
+            anomalies: 0,
+            ai_analyze: 0,
+            regex_analyze: 0,
+            time_ms,
+            error: Some(error),
+        }
+    }
+}
+
+/// Represents the aggregate summary of a batch scan, with the totals over all the files and the summary of each file.
+#[derive(Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct BatchSummary {
+    pub analysed_files: usize,
+    pub failed_files: usize,
+    pub anomalies: usize,
+    pub ai_analyze: u32,
+    pub regex_analyze: u32,
+    pub time_ms: u128,
+    pub files: Vec<FileSummary>,
+}
+
+impl BatchSummary {
+    /// Create a `BatchSummary` by adding up the summaries of the files.
+    #[must_use]
+    pub fn new(files: Vec<FileSummary>, time_ms: u128) -> Self {
+        let failed_files: usize = files.iter().filter(|file| file.error.is_some()).count();
+
+        Self {
+            analysed_files: files.len() - failed_files,
+            failed_files,
+            anomalies: files.iter().map(|file| file.anomalies).sum(),
+            ai_analyze: files.iter().map(|file| file.ai_analyze).sum(),
+            regex_analyze: files.iter().map(|file| file.regex_analyze).sum(),
+            time_ms,
+            files,
+        }
+    }
+
+    /// Save the `BatchSummary` to a file in pretty JSON format
+    pub fn save_to_file(&self, file_path: &Path) -> Result<(), DataLintError> {
+        self.write_to(std::fs::File::create(file_path)?)
+    }
+
+    /// Write the `BatchSummary` in pretty JSON format to any writer, such as the standard output
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), DataLintError> {
+        let mut writer: BufWriter<W> = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(std::io::Error::from)?;
+        writeln!(writer)?;
+        Ok(writer.flush()?)
+    }
+}
@@ -0,0 +1,63 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::output_format::OutputFormat;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::json_output::JsonOutput;
+use crate::structs::report_summary::ReportSummary;
+use crate::structs::sarif_output::SarifOutput;
+use crate::structs::structure_finding::StructureFinding;
+use crate::traits::report_writer::ReportWriter;
+use std::io::Write;
+
+/// Writes the reports holding every anomaly in a single document, pretty JSON or SARIF,
+/// the anomalies and the structural findings being kept in memory until the analysis is finished.
+#[non_exhaustive]
+pub struct BufferedReportWriter {
+    format: OutputFormat,
+    anomalies: Vec<Anomaly>,
+    findings: Vec<StructureFinding>,
+    writer: Box<dyn Write>,
+}
+
+impl BufferedReportWriter {
+    /// Create a new `BufferedReportWriter` writing the report in the given format.
+    #[inline]
+    #[must_use]
+    pub fn new(format: OutputFormat, writer: Box<dyn Write>) -> Self {
+        Self {
+            format,
+            anomalies: Vec::new(),
+            findings: Vec::new(),
+            writer,
+        }
+    }
+}
+
+impl ReportWriter for BufferedReportWriter {
+    fn write_anomaly(&mut self, anomaly: Anomaly) -> Result<(), DataLintError> {
+        self.anomalies.push(anomaly);
+        Ok(())
+    }
+
+    fn write_finding(&mut self, finding: StructureFinding) -> Result<(), DataLintError> {
+        self.findings.push(finding);
+        Ok(())
+    }
+
+    fn finish(&mut self, summary: &ReportSummary) -> Result<(), DataLintError> {
+        let mut report: JsonOutput = JsonOutput::new(
+            std::mem::take(&mut self.anomalies),
+            summary.analysed_file.clone(),
+            summary.ai_analyze,
+            summary.regex_analyze,
+            summary.time_ms,
+        );
+        report.encoding.clone_from(&summary.encoding);
+        report.columns.clone_from(&summary.columns);
+        report.structure = std::mem::take(&mut self.findings);
+
+        match self.format {
+            OutputFormat::Sarif => SarifOutput::new(&report).write_to(&mut self.writer),
+            _ => report.write_to(&mut self.writer),
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use crate::enums::clean_mode::CleanMode;
+use crate::enums::command::Command;
+use crate::enums::output_format::OutputFormat;
+use clap::{ArgAction, Args, Parser};
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+/// Represents the command line interface of DataLint.
+#[derive(Parser)]
+#[command(name = "datalint", version, about)]
+#[non_exhaustive]
+pub struct Cli {
+    /// Path to the configuration file (defaults to `config.json` in the working directory, then next to the executable)
+    #[arg(short, long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Score above which a value is reported as an anomaly, overrides the configuration
+    #[arg(short, long, global = true, value_parser = parse_threshold)]
+    pub threshold: Option<f64>,
+
+    /// Threshold applied to a single column, as `<header>=<score>` (can be repeated)
+    #[arg(long = "column-threshold", global = true, value_name = "HEADER=SCORE", value_parser = parse_column_threshold)]
+    pub column_thresholds: Vec<(String, f64)>,
+
+    /// Path to a TOML or JSON file of allow and deny rules, overrides the rules file of the configuration
+    #[arg(short, long, global = true, value_name = "PATH")]
+    pub rules: Option<PathBuf>,
+
+    /// Increase the verbosity of the output (can be repeated)
+    #[arg(short, long, global = true, action = ArgAction::Count, conflicts_with = "quiet")]
+    pub verbose: u8,
+
+    /// Only print errors
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+/// Arguments of the `scan` subcommand.
+#[derive(Args)]
+#[non_exhaustive]
+pub struct ScanArgs {
+    /// Path to the CSV file to analyse, `-` reads it from the standard input.
+    /// A directory or a glob pattern such as `data/*.csv` scans every matching file
+    pub input: PathBuf,
+
+    /// Path of the report to write, `-` writes it to the standard output
+    /// (defaults to `json/<input name>.<format>`, or the standard output when reading from the standard input).
+    /// When scanning several files, directory of the reports and of the summary (defaults to `json`)
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Format of the report
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Json)]
+    pub format: OutputFormat,
+
+    /// Delimiter of the CSV file, as a single character or `tab`, detected when not given
+    #[arg(short, long, value_parser = parse_delimiter)]
+    pub delimiter: Option<u8>,
+
+    /// Number of files analysed in parallel when scanning several files (defaults to the number of CPUs)
+    #[arg(short, long, value_name = "N")]
+    pub jobs: Option<NonZeroUsize>,
+
+    /// Path of a copy of the CSV file without the flagged rows, or with the flagged cells blanked (single file only)
+    #[arg(long, value_name = "PATH")]
+    pub clean: Option<PathBuf>,
+
+    /// What is removed from the clean copy of the CSV file
+    #[arg(long, value_enum, default_value_t = CleanMode::Rows)]
+    pub clean_mode: CleanMode,
+
+    /// Path of a CSV file holding the flagged rows with the reason and the score of their anomalies (single file only)
+    #[arg(long, value_name = "PATH")]
+    pub quarantine: Option<PathBuf>,
+
+    /// Path of a profile saved by the `profile` command, the scanned file being compared to it (single file only)
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+}
+
+/// Arguments of the `profile` subcommand.
+#[derive(Args)]
+#[non_exhaustive]
+pub struct ProfileArgs {
+    /// Path to the CSV file to profile, `-` reads it from the standard input
+    pub input: PathBuf,
+
+    /// Path of the profile to write, `-` writes it to the standard output
+    /// (defaults to `json/<input name>.profile.json`, or the standard output when reading from the standard input)
+    #[arg(short, long, value_name = "PATH")]
+    pub output: Option<PathBuf>,
+
+    /// Delimiter of the CSV file, as a single character or `tab`, detected when not given
+    #[arg(short, long, value_parser = parse_delimiter)]
+    pub delimiter: Option<u8>,
+
+    /// Path of a profile saved earlier, the new profile being compared to it
+    #[arg(long, value_name = "PATH")]
+    pub baseline: Option<PathBuf>,
+}
+
+/// Arguments of the `explain` subcommand.
+#[derive(Args)]
+#[non_exhaustive]
+pub struct ExplainArgs {
+    /// Value to classify
+    pub value: String,
+
+    /// Header of the column the value belongs to, used to pick the column threshold
+    #[arg(long)]
+    pub column: Option<String>,
+
+    /// Index of the column the value belongs to, starting at 0, used to find its type in a schema keyed by index
+    #[arg(long)]
+    pub column_index: Option<usize>,
+}
+
+/// Parse a threshold and check that it lies between 0 and 1.
+fn parse_threshold(value: &str) -> Result<f64, String> {
+    let threshold: f64 = value
+        .parse()
+        .map_err(|e| format!("`{value}` is not a number: {e}"))?;
+
+    if (0.0..=1.0).contains(&threshold) {
+        Ok(threshold)
+    } else {
+        Err(format!("`{value}` must be between 0 and 1"))
+    }
+}
+
+/// Parse a `<header>=<score>` column threshold.
+fn parse_column_threshold(value: &str) -> Result<(String, f64), String> {
+    let (column, threshold) = value
+        .rsplit_once('=')
+        .ok_or_else(|| format!("`{value}` must be formatted as <header>=<score>"))?;
+
+    if column.is_empty() {
+        return Err(format!("`{value}` has an empty column header"));
+    }
+
+    Ok((String::from(column), parse_threshold(threshold)?))
+}
+
+/// Parse a delimiter given as a single ASCII character, `tab` or `\\t`.
+fn parse_delimiter(value: &str) -> Result<u8, String> {
+    match value {
+        "tab" | "\\t" | "\t" => Ok(b'\t'),
+        _ if value.len() == 1 && value.is_ascii() => Ok(value.as_bytes()[0]),
+        _ => Err(format!(
+            "`{value}` must be a single ASCII character or `tab`"
+        )),
+    }
+}
@@ -0,0 +1,29 @@
+use crate::enums::column_type::ColumnType;
+use serde::{Deserialize, Serialize};
+
+/// Represents the profile of a column of a CSV file: its inferred type, the safe regex matching all its values,
+/// its empty values and distinct values, and the statistics used to detect a drift of its distribution.
+/// `min`, `max`, `mean` and `std_dev` are only given for numeric columns.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ColumnProfile {
+    pub name: String,
+    pub index: usize,
+    #[serde(flatten)]
+    pub column_type: ColumnType,
+    pub regex_class: String,
+    pub nullable: bool,
+    pub null_ratio: f64,
+    pub distinct_count: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub std_dev: Option<f64>,
+    pub min_length: usize,
+    pub max_length: usize,
+    pub mean_length: f64,
+}
@@ -0,0 +1,184 @@
+use crate::enums::column_type::ColumnType;
+use crate::structs::column_profile::ColumnProfile;
+use regex::RegexSet;
+use std::collections::HashSet;
+
+/// Formats tried when inferring the type of a date column, the first one matching every value being kept.
+const DATE_FORMATS: [&str; 8] = [
+    "%Y-%m-%d",
+    "%d/%m/%Y",
+    "%m/%d/%Y",
+    "%Y/%m/%d",
+    "%d-%m-%Y",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%d/%m/%Y %H:%M:%S",
+];
+
+/// Accumulates the values of a column one by one to build its `ColumnProfile`,
+/// keeping the types that still match every value seen so far.
+#[non_exhaustive]
+pub struct ColumnProfiler {
+    count: usize,
+    null_count: usize,
+    distinct_values: HashSet<String>,
+    is_integer: bool,
+    is_decimal: bool,
+    is_email: bool,
+    is_phone: bool,
+    date_formats: Vec<&'static str>,
+    regex_class_counts: Vec<usize>,
+    sum: f64,
+    sum_of_squares: f64,
+    min: f64,
+    max: f64,
+    length_sum: usize,
+    min_length: usize,
+    max_length: usize,
+}
+
+impl ColumnProfiler {
+    /// Maximum number of distinct values counted per column, so that memory use does not grow with the file size.
+    pub const MAX_DISTINCT_VALUES: usize = 100_000;
+
+    /// Create a profiler for a column whose values are classified by the given safe regex patterns.
+    #[must_use]
+    pub fn new(regex_class_count: usize) -> Self {
+        Self {
+            count: 0,
+            null_count: 0,
+            distinct_values: HashSet::new(),
+            is_integer: true,
+            is_decimal: true,
+            is_email: true,
+            is_phone: true,
+            date_formats: DATE_FORMATS.to_vec(),
+            regex_class_counts: vec![0; regex_class_count],
+            sum: 0.0,
+            sum_of_squares: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            length_sum: 0,
+            min_length: usize::MAX,
+            max_length: 0,
+        }
+    }
+
+    /// Add a value of the column, empty values only being counted as nulls.
+    pub fn add(&mut self, raw_value: &str, safe_regex_set: &RegexSet) {
+        let value: &str = raw_value.trim();
+        self.count += 1;
+
+        if value.is_empty() {
+            self.null_count += 1;
+            return;
+        }
+
+        if self.distinct_values.len() < Self::MAX_DISTINCT_VALUES {
+            self.distinct_values.insert(value.into());
+        }
+
+        for index in safe_regex_set.matches(value).iter() {
+            if let Some(class_count) = self.regex_class_counts.get_mut(index) {
+                *class_count += 1;
+            }
+        }
+
+        let length: usize = value.chars().count();
+        self.length_sum += length;
+        self.min_length = self.min_length.min(length);
+        self.max_length = self.max_length.max(length);
+
+        self.is_integer = self.is_integer && ColumnType::Integer.is_valid(value);
+        self.is_decimal = self.is_decimal && ColumnType::Decimal.is_valid(value);
+        self.is_email = self.is_email && ColumnType::Email.is_valid(value);
+        self.is_phone = self.is_phone && ColumnType::Phone.is_valid(value);
+        self.date_formats
+            .retain(|format| ColumnType::is_date(value, format));
+
+        if self.is_decimal
+            && let Ok(number) = value.replace(',', ".").parse::<f64>()
+        {
+            self.sum += number;
+            self.sum_of_squares += number * number;
+            self.min = self.min.min(number);
+            self.max = self.max.max(number);
+        }
+    }
+
+    /// Return the most specific type matching every value of the column, free text when there is none.
+    #[must_use]
+    fn column_type(&self) -> ColumnType {
+        if self.count == self.null_count {
+            ColumnType::Text
+        } else if self.is_integer {
+            ColumnType::Integer
+        } else if self.is_decimal {
+            ColumnType::Decimal
+        } else if let Some(format) = self.date_formats.first() {
+            ColumnType::Date {
+                format: String::from(*format),
+            }
+        } else if self.is_email {
+            ColumnType::Email
+        } else if self.is_phone {
+            ColumnType::Phone
+        } else {
+            ColumnType::Text
+        }
+    }
+
+    /// Build the profile of the column from the values added, the regex class being the name of the first
+    /// safe pattern matching every non-empty value, or `none`.
+    #[must_use]
+    pub fn finish(self, name: &str, index: usize, regex_class_names: &[&str]) -> ColumnProfile {
+        let non_null_count: usize = self.count - self.null_count;
+        let column_type: ColumnType = self.column_type();
+        let is_numeric: bool = matches!(column_type, ColumnType::Integer | ColumnType::Decimal);
+
+        let regex_class: &str = self
+            .regex_class_counts
+            .iter()
+            .zip(regex_class_names)
+            .find(|&(&class_count, _)| non_null_count > 0 && class_count == non_null_count)
+            .map_or("none", |(_, name)| name);
+
+        let (mean, std_dev): (Option<f64>, Option<f64>) = if is_numeric {
+            let count: f64 = non_null_count as f64;
+            let mean: f64 = self.sum / count;
+            let variance: f64 = (self.sum_of_squares / count - mean * mean).max(0.0);
+            (Some(mean), Some(variance.sqrt()))
+        } else {
+            (None, None)
+        };
+
+        ColumnProfile {
+            name: String::from(name),
+            index,
+            column_type,
+            regex_class: String::from(regex_class),
+            nullable: self.null_count > 0,
+            null_ratio: if self.count == 0 {
+                0.0
+            } else {
+                self.null_count as f64 / self.count as f64
+            },
+            distinct_count: self.distinct_values.len(),
+            min: is_numeric.then_some(self.min),
+            max: is_numeric.then_some(self.max),
+            mean,
+            std_dev,
+            min_length: if non_null_count == 0 {
+                0
+            } else {
+                self.min_length
+            },
+            max_length: self.max_length,
+            mean_length: if non_null_count == 0 {
+                0.0
+            } else {
+                self.length_sum as f64 / non_null_count as f64
+            },
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Represents the statistics of a column gathered while it is scanned: how many values it has,
+/// how many are discarded by the rules and its schema type, how many are reported by the detectors,
+/// how many go to the model and what they score.
+/// They show which columns are noisy and where the model spends its time.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct ColumnStats {
+    pub column: String,
+    pub column_index: usize,
+    pub rows: usize,
+    pub empty: usize,
+    pub distinct_estimate: usize,
+    pub prefilter_rejected: usize,
+    pub schema_violations: usize,
+    pub detected: usize,
+    pub sent_to_model: usize,
+    pub anomalies: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean_score: Option<f64>,
+    #[serde(skip)]
+    scored: usize,
+    #[serde(skip)]
+    lowest_hashes: BTreeSet<u64>,
+}
+
+impl ColumnStats {
+    /// Number of lowest value hashes kept to estimate the distinct count, which is exact below it.
+    pub const DISTINCT_SAMPLE_SIZE: usize = 1024;
+
+    /// Create the empty statistics of a column.
+    #[inline]
+    #[must_use]
+    pub fn new(column: &str, column_index: usize) -> Self {
+        Self {
+            column: String::from(column),
+            column_index,
+            ..Self::default()
+        }
+    }
+
+    /// Create the empty statistics of each column of the headers.
+    #[must_use]
+    pub fn from_headers<'a>(headers: impl IntoIterator<Item = &'a str>) -> Vec<Self> {
+        headers
+            .into_iter()
+            .enumerate()
+            .map(|(column_index, column)| Self::new(column, column_index))
+            .collect()
+    }
+
+    /// Count a value of the column, empty values apart, and update the estimate of its distinct values.
+    /// The estimate keeps the lowest hashes of the values seen, so that memory use does not grow with the file size.
+    pub fn add_value(&mut self, value: &str) {
+        self.rows += 1;
+        if value.is_empty() {
+            self.empty += 1;
+            return;
+        }
+
+        let mut hasher: DefaultHasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        let hash: u64 = hasher.finish();
+
+        if self.lowest_hashes.len() < Self::DISTINCT_SAMPLE_SIZE {
+            self.lowest_hashes.insert(hash);
+        } else if self
+            .lowest_hashes
+            .last()
+            .is_some_and(|&highest| hash < highest)
+            && self.lowest_hashes.insert(hash)
+        {
+            self.lowest_hashes.pop_last();
+        }
+
+        self.distinct_estimate = self.estimate_distinct();
+    }
+
+    /// Add the score given by the model to a value of the column to its mean score.
+    pub fn add_score(&mut self, score: f64) {
+        self.scored += 1;
+        let mean: f64 = self.mean_score.unwrap_or_default();
+        self.mean_score = Some(mean + (score - mean) / self.scored as f64);
+    }
+
+    /// Return the number of distinct values, exact while fewer hashes than the sample size were seen,
+    /// estimated from the highest of the lowest hashes otherwise.
+    fn estimate_distinct(&self) -> usize {
+        match self.lowest_hashes.last() {
+            Some(&highest) if self.lowest_hashes.len() >= Self::DISTINCT_SAMPLE_SIZE => {
+                let estimate: f64 =
+                    (Self::DISTINCT_SAMPLE_SIZE - 1) as f64 * (u64::MAX as f64 / highest as f64);
+                estimate.round() as usize
+            }
+            _ => self.lowest_hashes.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_column_stats() {
+        let mut stats: ColumnStats = ColumnStats::new("Comment", 2);
+        for value in ["a", "b", "", "a"] {
+            stats.add_value(value);
+        }
+        assert_eq!(
+            (stats.rows, stats.empty, stats.distinct_estimate),
+            (4, 1, 2)
+        );
+
+        assert_eq!(stats.mean_score, None);
+        stats.add_score(0.2);
+        stats.add_score(0.6);
+        assert!((stats.mean_score.unwrap() - 0.4).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_distinct_estimate() {
+        let mut stats: ColumnStats = ColumnStats::new("Id", 0);
+        for value in 0..20_000 {
+            stats.add_value(&value.to_string());
+            stats.add_value(&value.to_string());
+        }
+
+        let error: f64 = (stats.distinct_estimate as f64 - 20_000.0).abs() / 20_000.0;
+        assert!(error < 0.1, "estimate {}", stats.distinct_estimate);
+    }
+}
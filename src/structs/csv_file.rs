@@ -1,24 +1,43 @@
+use crate::enums::column_type::ColumnType;
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::detection_source::DetectionSource;
 use crate::enums::log_level::LogLevel;
-use crate::enums::separator::SeparatorType;
+use crate::enums::severity::Severity;
+use crate::structs::column_stats::ColumnStats;
+use crate::structs::detection::Detection;
+use crate::structs::field_region::FieldRegion;
 use crate::structs::inferable_value::InferableValue;
 use crate::structs::logger::{log_and_print_message, print_message};
-use crate::utils::regex::{get_safe_regex_set, get_unsafe_value_regex_set};
-use crate::utils::util::get_file_name;
-use csv::{Reader, ReaderBuilder, StringRecord};
-use rayon::iter::IntoParallelRefIterator;
-use rayon::prelude::ParallelIterator;
-use regex::RegexSet;
-use std::borrow::Cow;
-use std::collections::HashSet;
-use std::error::Error;
+use crate::structs::lru_cache::LruCache;
+use crate::structs::model::Model;
+use crate::structs::rule::Rule;
+use crate::structs::rule_matches::RuleMatches;
+use crate::structs::rule_set::RuleSet;
+use crate::structs::schema::Schema;
+use crate::structs::sniffer::{SniffResult, Sniffer};
+use crate::structs::structure_checker::StructureChecker;
+use crate::structs::structure_finding::StructureFinding;
+use crate::structs::utf8_fallback_reader::Utf8FallbackReader;
+use crate::utils::encoding::detect_encoding;
+use crate::utils::util::{STD_STREAM, is_std_stream};
+use csv::{Reader, ReaderBuilder, StringRecord, Trim};
+use encoding_rs::{Encoding, UTF_8};
+use encoding_rs_io::DecodeReaderBytesBuilder;
 use std::fs::File;
 use std::io;
-use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::io::{BufRead, BufReader, Cursor, Read};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Represents a CSV file with its path and separator.
+/// Represents a CSV file with its path, separator and character encoding.
+/// The path `-` designates the standard input, which can only be read once.
 pub struct CsvFile {
     pub csv_file_path: String,
     pub separator: u8, // The separator used in the CSV file, represented as a byte like b',', b';', etc.
+    pub separator_confidence: f32, // Confidence of the separator detection, 1 when the separator is given
+    pub encoding: &'static Encoding, // Encoding of the data, transcoded to UTF-8 while it is read
+    ascii_sample: bool, // True when the inspected beginning is pure ASCII, so that the rest may not be UTF-8
+    stdin_head: Vec<u8>, // Bytes already read from the standard input to inspect it, replayed by `open_reader`
+    stdin_consumed: AtomicBool,
 }
 
 impl CsvFile {
@@ -29,44 +48,78 @@ impl CsvFile {
         Self {
             csv_file_path: String::from(csv_file_path),
             separator,
+            separator_confidence: 1.0,
+            encoding: UTF_8,
+            ascii_sample: false,
+            stdin_head: Vec::new(),
+            stdin_consumed: AtomicBool::new(false),
         }
     }
 
-    /// Return the separator as a char.
+    /// Return true if the CSV data is read from the standard input.
     #[inline]
     #[must_use]
-    pub fn find_separator_in_file(csv_file_path: &str) -> SeparatorType {
-        const POSSIBLE_SEPARATORS: [SeparatorType; 6] = [
-            SeparatorType::Semicolon,
-            SeparatorType::Tab,
-            SeparatorType::Pipe,
-            SeparatorType::Null,
-            SeparatorType::Comma,
-            SeparatorType::Invalid,
-        ];
-
-        let first_line: String = Self::read_first_line(csv_file_path).unwrap();
-
-        POSSIBLE_SEPARATORS
-            .par_iter()
-            .find_any(|sep| first_line.contains(sep.as_char()))
-            .cloned()
-            .ok_or_else(|| {
-                io::Error::new(
-                    io::ErrorKind::InvalidData,
-                    "Error: No valid separator found in the CSV file.",
-                )
-            })
-            .unwrap()
+    pub fn is_stdin(&self) -> bool {
+        is_std_stream(&self.csv_file_path)
     }
 
-    /// Return the headers of the CSV file as a `StringRecord`.
-    pub fn get_headers(&self) -> Result<StringRecord, Box<dyn Error>> {
-        let binding: String = Self::read_first_line(&self.csv_file_path)?;
-        let first_line: &str = binding.trim();
+    /// Open a reader over the whole CSV data, from the file or from the standard input, transcoded to UTF-8.
+    pub fn open_reader(&self) -> Result<Box<dyn Read>, DataLintError> {
+        if !self.is_stdin() {
+            return Ok(self.decode(File::open(&self.csv_file_path)?));
+        }
+
+        if self.stdin_consumed.swap(true, Ordering::Relaxed) {
+            return Err(DataLintError::Io(io::Error::other(
+                "The standard input can only be read once",
+            )));
+        }
+
+        Ok(self.decode(Cursor::new(self.stdin_head.clone()).chain(io::stdin())))
+    }
+
+    /// Wrap a raw reader so that it yields UTF-8, transcoding from the encoding of the file and dropping its BOM.
+    /// Data whose inspected beginning is pure ASCII falls back to Windows-1252 at its first invalid UTF-8 sequence.
+    fn decode<'a>(&self, raw_reader: impl Read + 'a) -> Box<dyn Read + 'a> {
+        if self.ascii_sample {
+            return Box::new(Utf8FallbackReader::new(raw_reader));
+        }
+
+        Box::new(
+            DecodeReaderBytesBuilder::new()
+                .encoding(Some(self.encoding))
+                .bom_override(true)
+                .strip_bom(true)
+                .build(raw_reader),
+        )
+    }
+
+    /// Return the `ReaderBuilder` shared by the headers and the records, so that their fields line up.
+    #[inline]
+    #[must_use]
+    pub fn reader_builder(&self) -> ReaderBuilder {
+        let mut builder: ReaderBuilder = ReaderBuilder::new();
+        builder
+            .delimiter(self.separator)
+            .has_headers(true)
+            .trim(Trim::Headers);
+        builder
+    }
+
+    /// Return the headers of the CSV file as a `StringRecord`, parsed like the records so that quoted headers are kept whole.
+    pub fn get_headers(&self) -> Result<StringRecord, DataLintError> {
+        let headers: StringRecord = if self.is_stdin() {
+            self.reader_builder()
+                .from_reader(self.decode(self.stdin_head.as_slice()))
+                .headers()?
+                .clone()
+        } else {
+            self.reader_builder()
+                .from_reader(self.open_reader()?)
+                .headers()?
+                .clone()
+        };
 
-        let headers: Vec<&str> = first_line.split(self.separator as char).collect();
-        let headers: StringRecord = headers.iter().map(|&s| s.to_string()).collect();
         Ok(headers)
     }
 
@@ -87,128 +140,167 @@ impl CsvFile {
         }
     }
 
-    /// Check if a file is encoded in UTF-8.
-    #[inline]
-    pub fn is_file_utf8(file_path: &str) -> Result<bool, Box<dyn Error>> {
-        const CHUNK_SIZE: usize = 16 * 1024; // 16 KB
-
-        let file: File = File::open(file_path)?;
-        let mut reader: BufReader<File> = BufReader::new(file);
-        let mut buffer: Vec<u8> = vec![0_u8; CHUNK_SIZE];
-
-        while let Ok(bytes_read) = reader.read(&mut buffer) {
-            if bytes_read == 0 {
-                break;
-            }
+    /// Create a `CsvFile` instance from a file path, checking its encoding and separator.
+    /// The path `-` reads the CSV data from the standard input.
+    pub fn from_file(csv_file_path: &str) -> Result<Self, DataLintError> {
+        Self::from_file_with_separator(csv_file_path, None)
+    }
 
-            if std::str::from_utf8(&buffer[..bytes_read]).is_err() {
-                return Ok(false);
-            }
+    /// Create a `CsvFile` instance from a file path, detecting its encoding.
+    /// The separator is detected from the first records unless one is given.
+    pub fn from_file_with_separator(
+        csv_file_path: &str,
+        separator: Option<u8>,
+    ) -> Result<Self, DataLintError> {
+        if is_std_stream(csv_file_path) {
+            return Self::from_stdin(separator);
         }
 
-        Ok(true)
+        let sample: Vec<u8> = Self::read_sample(&mut BufReader::new(File::open(csv_file_path)?))?;
+        let encoding: &'static Encoding = detect_encoding(&sample);
+        let (separator, separator_confidence): (u8, f32) =
+            Self::detect_separator(&sample, encoding, separator)?;
+
+        Ok(Self {
+            separator_confidence,
+            encoding,
+            ascii_sample: sample.is_ascii(),
+            ..Self::new(csv_file_path, separator)
+        })
     }
 
-    /// Convert a file to UTF-8 encoding and save it in the "utf8" directory.
-    #[inline]
-    pub fn convert_file_to_utf8(input_path: &str) -> Result<String, Box<dyn Error>> {
-        const UTF8: &str = "utf8";
-
-        if !std::path::Path::new(UTF8).exists()
-            && let Err(e) = std::fs::create_dir(UTF8)
-        {
-            log_and_print_message(
-                &format!("Error creating 'utf8' directory: {e}"),
-                &LogLevel::Error,
-            );
-        }
+    /// Create a `CsvFile` instance reading from the standard input.
+    /// The beginning of the input is buffered to detect the encoding and the separator, and replayed when the data is read.
+    pub fn from_stdin(separator: Option<u8>) -> Result<Self, DataLintError> {
+        let stdin_head: Vec<u8> = Self::read_sample(&mut io::stdin().lock())?;
+        let encoding: &'static Encoding = detect_encoding(&stdin_head);
+        let (separator, separator_confidence): (u8, f32) =
+            Self::detect_separator(&stdin_head, encoding, separator)?;
+
+        Ok(Self {
+            separator_confidence,
+            encoding,
+            ascii_sample: stdin_head.is_ascii(),
+            stdin_head,
+            ..Self::new(STD_STREAM, separator)
+        })
+    }
 
-        let encoded_file_name: String = format!("utf8/{}_utf8.csv", get_file_name(input_path));
+    /// Read the beginning of the data, at least its whole first line, to inspect it.
+    fn read_sample(reader: &mut impl BufRead) -> io::Result<Vec<u8>> {
+        const SAMPLE_SIZE: u64 = 64 * 1024; // 64 KB
 
-        let output_file: File = match File::create(&encoded_file_name) {
-            Ok(file) => file,
-            Err(e) => {
-                log_and_print_message(
-                    &format!("Error creating output file: {e}"),
-                    &LogLevel::Error,
-                );
-                std::process::exit(1);
-            }
-        };
+        let mut sample: Vec<u8> = Vec::new();
+        reader.read_until(b'\n', &mut sample)?;
+        reader.take(SAMPLE_SIZE).read_to_end(&mut sample)?;
+        Ok(sample)
+    }
 
-        let input_file: File = File::open(input_path)?;
-        let mut reader: BufReader<File> = BufReader::new(input_file);
-        let mut writer: BufWriter<File> = BufWriter::new(output_file);
+    /// Return the given separator, or the one detected in the decoded sample with its confidence.
+    fn detect_separator(
+        sample: &[u8],
+        encoding: &'static Encoding,
+        separator: Option<u8>,
+    ) -> Result<(u8, f32), DataLintError> {
+        const LOW_CONFIDENCE: f32 = 0.75;
 
-        let mut buffer: Vec<u8> = Vec::new();
-        reader.read_to_end(&mut buffer)?;
+        if let Some(separator) = separator {
+            return Ok((separator, 1.0));
+        }
 
-        let decoded: Cow<str> = String::from_utf8_lossy(&buffer);
-        writer.write_all(decoded.as_bytes())?;
-        Ok(encoded_file_name)
-    }
+        let (decoded_sample, _) = encoding.decode_with_bom_removal(sample);
+        let result: SniffResult = Sniffer::sniff(decoded_sample.as_bytes()).ok_or_else(|| {
+            DataLintError::Csv(String::from(
+                "Unable to detect a valid separator in the CSV file",
+            ))
+        })?;
 
-    /// Create a `CsvFile` instance from a file path, checking its encoding and separator.
-    pub fn from_file(csv_file_path: &str) -> Result<Self, Box<dyn Error>> {
-        let is_utf8: bool = Self::is_file_utf8(csv_file_path).map_err(|e| {
+        if result.confidence < LOW_CONFIDENCE {
             log_and_print_message(
-                &format!("Error checking file encoding: {e}",),
-                &LogLevel::Error,
+                &format!(
+                    "Separator {:?} detected with a low confidence of {:.2}, use --delimiter to override it",
+                    result.delimiter as char, result.confidence
+                ),
+                &LogLevel::Info,
             );
-            e
-        })?;
-
-        let csv_file_path: String = if !is_utf8 {
-            Self::convert_file_to_utf8(csv_file_path).map_err(|e| {
-                log_and_print_message(
-                    &format!("Error converting file to UTF-8: {e}"),
-                    &LogLevel::Error,
-                );
-                e
-            })?
-        } else {
-            String::from(csv_file_path)
-        };
-
-        let separator: u8 = u8::from(match Self::find_separator_in_file(&csv_file_path) {
-            SeparatorType::Comma => SeparatorType::Comma,
-            SeparatorType::Semicolon => SeparatorType::Semicolon,
-            SeparatorType::Tab => SeparatorType::Tab,
-            SeparatorType::Pipe => SeparatorType::Pipe,
-            SeparatorType::Null => SeparatorType::Null,
-            SeparatorType::Invalid => {
-                log_and_print_message(
-                    "Error: Unable to detect a valid separator in the CSV file.",
-                    &LogLevel::Error,
-                );
-                std::process::exit(1);
-            }
-        });
+        }
 
-        Ok(Self::new(&csv_file_path, separator))
+        Ok((result.delimiter, result.confidence))
     }
 
-    /// Collect unsafe values from the CSV file based on regex patterns.
+    /// Collect unsafe values from the CSV file based on the rules of the rule set and the types of the schema,
+    /// gathering the statistics of each column and the structural findings of the file during the same pass.
     #[inline]
     pub fn collect_unsafe_value(
         &self,
         csv_file_struct: &Self,
+        rule_set: &RuleSet,
+        schema: &Schema,
         regex_analyze: &mut u32,
-    ) -> Result<Vec<InferableValue>, Box<dyn Error>> {
-        let csv_file: File = File::open(&csv_file_struct.csv_file_path)?;
-        let mut rdr: Reader<File> = ReaderBuilder::new()
-            .delimiter(csv_file_struct.separator)
-            .has_headers(true)
-            .from_reader(csv_file);
-
-        let safe_regex_set: RegexSet = get_safe_regex_set(); // Regex for safe values
-        let unsafe_regex_set: RegexSet = get_unsafe_value_regex_set(); // Regex for unsafe values
-        let mut seen_words: HashSet<String> = HashSet::new(); // Store seen words to avoid duplicates
+        column_stats: &mut Vec<ColumnStats>,
+        structure_findings: &mut Vec<StructureFinding>,
+    ) -> Result<Vec<InferableValue>, DataLintError> {
         let mut batch_data: Vec<InferableValue> = Vec::new();
 
-        for (row_number, record) in rdr.records().enumerate() {
-            let record: StringRecord = match record {
-                Ok(record) => record,
+        *structure_findings = csv_file_struct.stream_unsafe_values(
+            usize::MAX,
+            Model::DEFAULT_CACHE_SIZE,
+            rule_set,
+            schema,
+            regex_analyze,
+            column_stats,
+            |chunk, _| {
+                batch_data.extend(chunk);
+                Ok(())
+            },
+        )?;
+
+        Ok(batch_data)
+    }
+
+    /// Read the records one by one and pass the values matching a deny rule and no allow rule of their column
+    /// to `on_chunk`, in chunks of at most `chunk_size` values, so that memory use does not grow with the file size.
+    /// The values of the columns typed by the schema are passed only when they do not match their type.
+    /// Each occurrence of an unsafe value is passed, the rule patterns being matched once per distinct value
+    /// among the last `cache_size` unsafe values seen.
+    /// Return through `regex_analyze` the number of values discarded by the rules, and through `column_stats`
+    /// the statistics of each column, also given to `on_chunk` so that it adds the scores and anomalies of the chunk.
+    /// Return the structural findings of the file, checked while the records are read.
+    #[allow(clippy::too_many_arguments)]
+    pub fn stream_unsafe_values<F>(
+        &self,
+        chunk_size: usize,
+        cache_size: usize,
+        rule_set: &RuleSet,
+        schema: &Schema,
+        regex_analyze: &mut u32,
+        column_stats: &mut Vec<ColumnStats>,
+        mut on_chunk: F,
+    ) -> Result<Vec<StructureFinding>, DataLintError>
+    where
+        F: FnMut(Vec<InferableValue>, &mut [ColumnStats]) -> Result<(), DataLintError>,
+    {
+        const INITIAL_CAPACITY: usize = 1024;
+        let chunk_size: usize = chunk_size.max(1);
+
+        // Ragged rows are reported by the structure checker and their fields are still analysed
+        let mut rdr: Reader<StructureChecker<Box<dyn Read>>> = self
+            .reader_builder()
+            .flexible(true)
+            .from_reader(StructureChecker::new(self.open_reader()?, self.separator));
+        let headers: StringRecord = rdr.headers()?.clone();
+        let column_types: Vec<Option<&ColumnType>> = schema.column_types(&headers);
+        *column_stats = ColumnStats::from_headers(&headers);
+
+        let mut seen_words: LruCache<String, RuleMatches> = LruCache::new(cache_size); // Rules matched by the seen words
+        let mut chunk: Vec<InferableValue> = Vec::with_capacity(chunk_size.min(INITIAL_CAPACITY));
+        let mut record: StringRecord = StringRecord::new();
+
+        // The records are read one at a time so that the structure checker gives the regions of their fields
+        for row_number in 0.. {
+            match rdr.read_record(&mut record) {
+                Ok(true) => {}
+                Ok(false) => break,
                 Err(e) => {
                     print_message(
                         &format!("Error reading record at row {row_number}: {e}"),
@@ -216,31 +308,129 @@ impl CsvFile {
                     );
                     continue;
                 }
-            };
+            }
+            let line: u32 = record.position().map_or(0, |position| {
+                u32::try_from(position.line()).unwrap_or(u32::MAX)
+            });
+            let regions: Vec<FieldRegion> =
+                rdr.get_mut().take_field_regions(line).unwrap_or_default();
 
             for (column_index, raw_value) in record.iter().enumerate() {
                 let value: &str = raw_value.trim();
+                let region: Option<FieldRegion> = regions.get(column_index).copied();
+                let column_name: &str = headers.get(column_index).unwrap_or_default();
 
-                if value.is_empty() || seen_words.contains(value) || safe_regex_set.is_match(value)
-                {
-                    *regex_analyze += 1;
-                    continue;
+                // Records longer than the headers get the statistics of their extra columns
+                if column_stats.len() <= column_index {
+                    column_stats.push(ColumnStats::new(column_name, column_index));
                 }
-
-                if !unsafe_regex_set.is_match(value) {
-                    continue;
+                let stats: &mut ColumnStats = &mut column_stats[column_index];
+                stats.add_value(value);
+
+                let data: InferableValue = match column_types.get(column_index).copied().flatten() {
+                    Some(ColumnType::Skip) => continue,
+                    _ if value.is_empty() => {
+                        *regex_analyze += 1;
+                        continue;
+                    }
+                    // Values reported by a detector are anomalies whatever their type and the rules of every column
+                    _ if let Some(detection) =
+                        rule_set.detect_in_column(raw_value, column_name) =>
+                    {
+                        stats.detected += 1;
+
+                        InferableValue {
+                            value: value.into(),
+                            line,
+                            column_index,
+                            region,
+                            matched_patterns: Vec::new(),
+                            severity: detection.severity,
+                            detection: Some(detection),
+                        }
+                    }
+                    // Typed values are checked against their type instead of the rules and the model
+                    Some(column_type) if column_type.is_checked() => {
+                        if column_type.is_valid(value) {
+                            *regex_analyze += 1;
+                            stats.prefilter_rejected += 1;
+                            continue;
+                        }
+
+                        stats.schema_violations += 1;
+
+                        InferableValue {
+                            value: value.into(),
+                            line,
+                            column_index,
+                            region,
+                            matched_patterns: Vec::new(),
+                            severity: Severity::Medium,
+                            detection: Some(Detection::new(
+                                DetectionSource::Schema,
+                                vec![format!("schema:{}", column_type.as_str())],
+                                Severity::Medium,
+                            )),
+                        }
+                    }
+                    _ => {
+                        let denied_by: Vec<&Rule> =
+                            if let Some(rule_matches) = seen_words.get(value) {
+                                rule_set.denied_by(rule_matches, column_name)
+                            } else {
+                                // Values matching a deny rule are kept so that their next occurrences are not matched again
+                                let rule_matches: RuleMatches = rule_set.matches(value);
+                                let denied_by: Vec<&Rule> =
+                                    rule_set.denied_by(&rule_matches, column_name);
+                                if rule_matches.is_denied() {
+                                    seen_words.insert(value.into(), rule_matches);
+                                }
+                                denied_by
+                            };
+
+                        if denied_by.is_empty() {
+                            *regex_analyze += 1;
+                            stats.prefilter_rejected += 1;
+                            continue;
+                        }
+
+                        stats.sent_to_model += 1;
+
+                        InferableValue {
+                            value: value.into(),
+                            line,
+                            column_index,
+                            region,
+                            matched_patterns: denied_by
+                                .iter()
+                                .map(|rule| rule.name.clone())
+                                .collect(),
+                            severity: denied_by
+                                .iter()
+                                .map(|rule| rule.severity)
+                                .max()
+                                .unwrap_or_default(),
+                            detection: None,
+                        }
+                    }
+                };
+
+                chunk.push(data);
+
+                if chunk.len() >= chunk_size {
+                    let full_chunk: Vec<InferableValue> = std::mem::replace(
+                        &mut chunk,
+                        Vec::with_capacity(chunk_size.min(INITIAL_CAPACITY)),
+                    );
+                    on_chunk(full_chunk, column_stats)?;
                 }
-
-                seen_words.insert(value.into());
-
-                batch_data.push(InferableValue {
-                    value: value.into(),
-                    row_number,
-                    column_index,
-                });
             }
         }
 
-        Ok(batch_data)
+        if !chunk.is_empty() {
+            on_chunk(chunk, column_stats)?;
+        }
+
+        Ok(rdr.into_inner().finish())
     }
 }
@@ -0,0 +1,82 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::report_summary::ReportSummary;
+use crate::structs::structure_finding::StructureFinding;
+use crate::traits::report_writer::ReportWriter;
+use csv::Writer;
+use std::io::Write;
+
+/// Writes a CSV report with one anomaly per row as it is found, the rows being buffered until the report is complete,
+/// the structural findings having no value nor score and a `structure:<issue>` reason.
+/// Values that a spreadsheet would read as a formula are prefixed with a quote so that opening the report is safe.
+#[non_exhaustive]
+pub struct CsvReportWriter {
+    writer: Writer<Box<dyn Write>>,
+}
+
+impl CsvReportWriter {
+    /// Columns of the report.
+    const HEADERS: [&'static str; 5] = ["line", "column", "value", "score", "reason"];
+
+    /// Characters that make a spreadsheet evaluate a cell as a formula.
+    const FORMULA_PREFIXES: [char; 6] = ['=', '+', '-', '@', '\t', '\r'];
+
+    /// Create a new `CsvReportWriter` writing to the given writer, starting with the header row.
+    pub fn new(writer: Box<dyn Write>) -> Result<Self, DataLintError> {
+        let mut writer: Writer<Box<dyn Write>> = Writer::from_writer(writer);
+        writer.write_record(Self::HEADERS)?;
+        Ok(Self { writer })
+    }
+
+    /// Return the value with a leading quote if a spreadsheet would evaluate it as a formula.
+    #[must_use]
+    fn escape_formula(value: &str) -> String {
+        if value.starts_with(Self::FORMULA_PREFIXES) {
+            format!("'{value}")
+        } else {
+            String::from(value)
+        }
+    }
+}
+
+impl ReportWriter for CsvReportWriter {
+    fn write_anomaly(&mut self, anomaly: Anomaly) -> Result<(), DataLintError> {
+        self.writer.write_record([
+            anomaly.line.to_string(),
+            Self::escape_formula(&anomaly.column),
+            Self::escape_formula(&anomaly.value),
+            anomaly.score.to_string(),
+            anomaly.reason(),
+        ])?;
+        Ok(())
+    }
+
+    fn write_finding(&mut self, finding: StructureFinding) -> Result<(), DataLintError> {
+        self.writer.write_record([
+            finding.line.to_string(),
+            Self::escape_formula(finding.column.as_deref().unwrap_or_default()),
+            String::new(),
+            String::new(),
+            format!("structure:{}: {}", finding.issue.as_str(), finding.message),
+        ])?;
+        Ok(())
+    }
+
+    fn finish(&mut self, _summary: &ReportSummary) -> Result<(), DataLintError> {
+        Ok(self.writer.flush()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_escape_formula() {
+        assert_eq!(CsvReportWriter::escape_formula("=1+1"), "'=1+1");
+        assert_eq!(CsvReportWriter::escape_formula("@SUM(A1)"), "'@SUM(A1)");
+        assert_eq!(CsvReportWriter::escape_formula("-2"), "'-2");
+        assert_eq!(CsvReportWriter::escape_formula("John"), "John");
+        assert_eq!(CsvReportWriter::escape_formula(""), "");
+    }
+}
@@ -0,0 +1,204 @@
+use crate::enums::clean_mode::CleanMode;
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::log_level::LogLevel;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::csv_file::CsvFile;
+use crate::structs::logger::print_message;
+use csv::{QuoteStyle, StringRecord, Writer, WriterBuilder};
+use std::collections::HashMap;
+use std::io::{Read, Write};
+
+/// Represents the score and the reason of a cell whose value was reported as an anomaly.
+struct FlaggedValue {
+    score: f32,
+    reason: String,
+}
+
+/// Splits a CSV file into a clean copy, without the flagged rows or with the flagged cells blanked,
+/// and a quarantine file holding the flagged rows with the reason and the score of their anomalies.
+/// A cell is flagged when its value was reported as an anomaly in its record and column, the same value
+/// elsewhere in the file being kept unless it was reported too.
+/// Both outputs keep the separator of the CSV file and quote the fields only where needed.
+#[derive(Default)]
+#[non_exhaustive]
+pub struct CsvSplitter {
+    flagged_cells: HashMap<(u32, usize), FlaggedValue>, // Flagged cells by line of their record and column index
+}
+
+impl CsvSplitter {
+    /// Header of the column added to the quarantine file with the reasons of the anomalies of the row.
+    pub const REASON_HEADER: &'static str = "datalint_reason";
+
+    /// Header of the column added to the quarantine file with the highest score of the row.
+    pub const SCORE_HEADER: &'static str = "datalint_score";
+
+    /// Create a new `CsvSplitter` without any flagged cell.
+    #[inline]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flag the cell of an anomaly, keeping its highest score.
+    pub fn add_anomaly(&mut self, anomaly: &Anomaly) {
+        let cell: (u32, usize) = (anomaly.line, anomaly.column_index);
+        match self.flagged_cells.get_mut(&cell) {
+            Some(flagged) if anomaly.score <= flagged.score => {}
+            Some(flagged) => {
+                flagged.score = anomaly.score;
+                flagged.reason = anomaly.reason();
+            }
+            None => {
+                self.flagged_cells.insert(
+                    cell,
+                    FlaggedValue {
+                        score: anomaly.score,
+                        reason: anomaly.reason(),
+                    },
+                );
+            }
+        }
+    }
+
+    /// Return true if no cell is flagged.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.flagged_cells.is_empty()
+    }
+
+    /// Read the CSV file again and write its clean copy and its quarantine file to the given writers.
+    /// Records that do not have as many fields as the headers are quarantined as they cannot be analysed.
+    /// Return the number of rows written to the clean copy and to the quarantine file.
+    pub fn split(
+        &self,
+        csv_file: &CsvFile,
+        clean_writer: Option<&mut dyn Write>,
+        clean_mode: CleanMode,
+        quarantine_writer: Option<&mut dyn Write>,
+    ) -> Result<(usize, usize), DataLintError> {
+        let mut rdr: csv::Reader<Box<dyn Read>> = csv_file
+            .reader_builder()
+            .flexible(true)
+            .from_reader(csv_file.open_reader()?);
+        let headers: StringRecord = rdr.headers()?.clone();
+
+        let mut clean: Option<Writer<&mut dyn Write>> =
+            clean_writer.map(|writer| Self::writer(csv_file, writer));
+        let mut quarantine: Option<Writer<&mut dyn Write>> =
+            quarantine_writer.map(|writer| Self::writer(csv_file, writer));
+
+        if let Some(clean) = &mut clean {
+            clean.write_record(&headers)?;
+        }
+        if let Some(quarantine) = &mut quarantine {
+            quarantine.write_record(
+                headers
+                    .iter()
+                    .chain([Self::REASON_HEADER, Self::SCORE_HEADER]),
+            )?;
+        }
+
+        let (mut clean_rows, mut quarantined_rows): (usize, usize) = (0, 0);
+
+        for (row_number, record) in rdr.records().enumerate() {
+            let record: StringRecord = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    print_message(
+                        &format!("Error reading record at row {row_number}: {e}"),
+                        &LogLevel::Error,
+                    );
+                    continue;
+                }
+            };
+            // The anomalies are located by the line where their record starts, as the scan reads it
+            let line: u32 = record.position().map_or(0, |position| {
+                u32::try_from(position.line()).unwrap_or(u32::MAX)
+            });
+
+            let (reasons, score): (Vec<String>, Option<f32>) = if record.len() == headers.len() {
+                self.record_flags(&record, &headers, line)
+            } else {
+                (
+                    vec![format!(
+                        "Unexpected number of fields: {} instead of {}",
+                        record.len(),
+                        headers.len()
+                    )],
+                    None,
+                )
+            };
+
+            if reasons.is_empty() {
+                if let Some(clean) = &mut clean {
+                    clean.write_record(&record)?;
+                    clean_rows += 1;
+                }
+                continue;
+            }
+
+            if let Some(quarantine) = &mut quarantine {
+                let score: String = score.map(|score| score.to_string()).unwrap_or_default();
+                quarantine.write_record(
+                    record
+                        .iter()
+                        .chain([reasons.join("; ").as_str(), score.as_str()]),
+                )?;
+                quarantined_rows += 1;
+            }
+
+            if let Some(clean) = &mut clean
+                && clean_mode == CleanMode::Cells
+                && record.len() == headers.len()
+            {
+                clean.write_record(record.iter().enumerate().map(|(column_index, value)| {
+                    if self.flagged_cells.contains_key(&(line, column_index)) {
+                        ""
+                    } else {
+                        value
+                    }
+                }))?;
+                clean_rows += 1;
+            }
+        }
+
+        if let Some(clean) = &mut clean {
+            clean.flush()?;
+        }
+        if let Some(quarantine) = &mut quarantine {
+            quarantine.flush()?;
+        }
+
+        Ok((clean_rows, quarantined_rows))
+    }
+
+    /// Return the reasons of the flagged cells of the record starting at the given line and their highest score.
+    fn record_flags(
+        &self,
+        record: &StringRecord,
+        headers: &StringRecord,
+        line: u32,
+    ) -> (Vec<String>, Option<f32>) {
+        let mut reasons: Vec<String> = Vec::new();
+        let mut max_score: Option<f32> = None;
+
+        for (column_index, header) in headers.iter().enumerate().take(record.len()) {
+            if let Some(flagged) = self.flagged_cells.get(&(line, column_index)) {
+                reasons.push(format!("{header}: {}", flagged.reason));
+                max_score = Some(max_score.map_or(flagged.score, |score| score.max(flagged.score)));
+            }
+        }
+
+        (reasons, max_score)
+    }
+
+    /// Return a CSV writer using the separator of the CSV file, accepting the malformed records being quarantined.
+    fn writer<'a>(csv_file: &CsvFile, writer: &'a mut dyn Write) -> Writer<&'a mut dyn Write> {
+        WriterBuilder::new()
+            .delimiter(csv_file.separator)
+            .quote_style(QuoteStyle::Necessary)
+            .flexible(true)
+            .from_writer(writer)
+    }
+}
@@ -0,0 +1,30 @@
+use crate::enums::detection_source::DetectionSource;
+use crate::enums::severity::Severity;
+
+/// Represents a value reported before the model, by a detector or by the schema,
+/// with the category of the detection, the rules it matched, its severity, its score and the lowest score
+/// the detector reports, both being 1 unless the detector scores the values it reports.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct Detection {
+    pub source: DetectionSource,
+    pub reasons: Vec<String>,
+    pub severity: Severity,
+    pub score: f64,
+    pub threshold: f64, // Lowest score of a value reported by the detector
+}
+
+impl Detection {
+    /// Create a new instance of `Detection`, with a score and a threshold of 1.
+    #[inline]
+    #[must_use]
+    pub const fn new(source: DetectionSource, reasons: Vec<String>, severity: Severity) -> Self {
+        Self {
+            source,
+            reasons,
+            severity,
+            score: 1.0,
+            threshold: 1.0,
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Represents where a field lies in a CSV file, quotes included: its start line and column, and the line and column
+/// of the character following it. Lines and columns start at 1, the columns counting Unicode code points.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FieldRegion {
+    pub start_line: u32,
+    pub start_column: u32,
+    pub end_line: u32,
+    pub end_column: u32,
+}
+
+impl FieldRegion {
+    /// Create a new instance of `FieldRegion`.
+    #[inline]
+    #[must_use]
+    pub const fn new(start: (u32, u32), end: (u32, u32)) -> Self {
+        Self {
+            start_line: start.0,
+            start_column: start.1,
+            end_line: end.0,
+            end_column: end.1,
+        }
+    }
+}
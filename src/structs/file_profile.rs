@@ -0,0 +1,245 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::drift_kind::DriftKind;
+use crate::enums::log_level::LogLevel;
+use crate::structs::column_profile::ColumnProfile;
+use crate::structs::column_profiler::ColumnProfiler;
+use crate::structs::csv_file::CsvFile;
+use crate::structs::logger::print_message;
+use crate::structs::schema_drift::SchemaDrift;
+use crate::utils::regex::safe_value_patterns;
+use csv::{Reader, StringRecord};
+use regex::RegexSet;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufWriter, Read, Write};
+use std::path::Path;
+
+/// Represents the profile of a CSV file, with the profile of each of its columns in the order of its headers.
+/// It is saved by the `profile` command and compared to the files scanned later to detect a drift of their layout.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct FileProfile {
+    pub analysed_file: String,
+    pub rows: usize,
+    pub columns: Vec<ColumnProfile>,
+}
+
+impl FileProfile {
+    /// Number of standard deviations of the profile above which a moved mean is reported.
+    pub const MEAN_SHIFT_STD_DEVS: f64 = 3.0;
+
+    /// Change of the ratio of empty values above which it is reported.
+    pub const NULL_RATIO_SHIFT: f64 = 0.2;
+
+    /// Relative change of the mean length of the values above which it is reported.
+    pub const MEAN_LENGTH_SHIFT: f64 = 0.5;
+
+    /// Read a CSV file and build the profile of each of its columns.
+    pub fn from_csv_file(csv_file: &CsvFile) -> Result<Self, DataLintError> {
+        let mut rdr: Reader<Box<dyn Read>> = csv_file
+            .reader_builder()
+            .from_reader(csv_file.open_reader()?);
+        let headers: StringRecord = rdr.headers()?.clone();
+
+        let patterns: Vec<(&str, String)> = safe_value_patterns().into_iter().collect();
+        let regex_class_names: Vec<&str> = patterns.iter().map(|(name, _)| *name).collect();
+        let safe_regex_set: RegexSet = RegexSet::new(patterns.iter().map(|(_, pattern)| pattern))
+            .map_err(|e| {
+            DataLintError::Config(format!("Error compiling the safe patterns: {e}"))
+        })?;
+
+        let mut profilers: Vec<ColumnProfiler> = headers
+            .iter()
+            .map(|_| ColumnProfiler::new(regex_class_names.len()))
+            .collect();
+        let mut rows: usize = 0;
+
+        for (row_number, record) in rdr.records().enumerate() {
+            let record: StringRecord = match record {
+                Ok(record) => record,
+                Err(e) => {
+                    print_message(
+                        &format!("Error reading record at row {row_number}: {e}"),
+                        &LogLevel::Error,
+                    );
+                    continue;
+                }
+            };
+
+            rows += 1;
+            for (profiler, value) in profilers.iter_mut().zip(record.iter()) {
+                profiler.add(value, &safe_regex_set);
+            }
+        }
+
+        let columns: Vec<ColumnProfile> = profilers
+            .into_iter()
+            .zip(headers.iter())
+            .enumerate()
+            .map(|(index, (profiler, name))| profiler.finish(name, index, &regex_class_names))
+            .collect();
+
+        Ok(Self {
+            analysed_file: csv_file.csv_file_path.clone(),
+            rows,
+            columns,
+        })
+    }
+
+    /// Load a profile saved in JSON format.
+    pub fn from_file(file_path: impl AsRef<Path>) -> Result<Self, DataLintError> {
+        let file_path: &Path = file_path.as_ref();
+        serde_json::from_reader(File::open(file_path)?).map_err(|e| {
+            DataLintError::Config(format!(
+                "Error reading profile from {}: {e}",
+                file_path.display()
+            ))
+        })
+    }
+
+    /// Compare the profile of a file to the profile saved for an earlier file and return their differences.
+    /// A column of the baseline missing from the file is considered renamed when a new column of the same type
+    /// is found at its position.
+    #[must_use]
+    pub fn compare(&self, baseline: &Self) -> Vec<SchemaDrift> {
+        let mut drifts: Vec<SchemaDrift> = Vec::new();
+        let mut matched_columns: Vec<bool> = vec![false; self.columns.len()];
+
+        for expected in &baseline.columns {
+            if let Some(index) = self.column_index(&expected.name) {
+                matched_columns[index] = true;
+                drifts.extend(Self::compare_column(&self.columns[index], expected));
+                continue;
+            }
+
+            let renamed: Option<&ColumnProfile> =
+                self.columns.get(expected.index).filter(|column| {
+                    baseline.column(&column.name).is_none()
+                        && column.column_type == expected.column_type
+                });
+
+            match renamed {
+                Some(column) => {
+                    matched_columns[column.index] = true;
+                    drifts.push(SchemaDrift::new(
+                        DriftKind::RenamedColumn,
+                        &expected.name,
+                        format!("renamed to {:?}", column.name),
+                    ));
+                    drifts.extend(Self::compare_column(column, expected));
+                }
+                None => drifts.push(SchemaDrift::new(
+                    DriftKind::MissingColumn,
+                    &expected.name,
+                    format!("missing, expected at position {}", expected.index),
+                )),
+            }
+        }
+
+        for (column, _) in self
+            .columns
+            .iter()
+            .zip(matched_columns)
+            .filter(|&(_, matched)| !matched)
+        {
+            drifts.push(SchemaDrift::new(
+                DriftKind::AddedColumn,
+                &column.name,
+                format!("added at position {}", column.index),
+            ));
+        }
+
+        drifts
+    }
+
+    /// Compare a column to its profile in the baseline.
+    fn compare_column(column: &ColumnProfile, expected: &ColumnProfile) -> Vec<SchemaDrift> {
+        let mut drifts: Vec<SchemaDrift> = Vec::new();
+
+        if column.column_type != expected.column_type {
+            drifts.push(SchemaDrift::new(
+                DriftKind::TypeChange,
+                &column.name,
+                format!(
+                    "type changed from {} to {}",
+                    expected.column_type.as_str(),
+                    column.column_type.as_str()
+                ),
+            ));
+        }
+
+        if column.nullable && !expected.nullable {
+            drifts.push(SchemaDrift::new(
+                DriftKind::NullabilityChange,
+                &column.name,
+                format!(
+                    "{:.1}% of empty values, none expected",
+                    column.null_ratio * 100.0
+                ),
+            ));
+        }
+
+        if let (Some(mean), Some(expected_mean), Some(expected_std_dev)) =
+            (column.mean, expected.mean, expected.std_dev)
+            && (mean - expected_mean).abs()
+                > (Self::MEAN_SHIFT_STD_DEVS * expected_std_dev).max(f64::EPSILON)
+        {
+            drifts.push(SchemaDrift::new(
+                DriftKind::DistributionShift,
+                &column.name,
+                format!("mean moved from {expected_mean:.3} to {mean:.3}"),
+            ));
+        }
+
+        if (column.null_ratio - expected.null_ratio).abs() > Self::NULL_RATIO_SHIFT {
+            drifts.push(SchemaDrift::new(
+                DriftKind::DistributionShift,
+                &column.name,
+                format!(
+                    "empty values moved from {:.1}% to {:.1}%",
+                    expected.null_ratio * 100.0,
+                    column.null_ratio * 100.0
+                ),
+            ));
+        }
+
+        if expected.mean_length > 0.0
+            && (column.mean_length - expected.mean_length).abs() / expected.mean_length
+                > Self::MEAN_LENGTH_SHIFT
+        {
+            drifts.push(SchemaDrift::new(
+                DriftKind::DistributionShift,
+                &column.name,
+                format!(
+                    "mean length moved from {:.1} to {:.1}",
+                    expected.mean_length, column.mean_length
+                ),
+            ));
+        }
+
+        drifts
+    }
+
+    /// Return the index of the column with the given header.
+    fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+
+    /// Return the profile of the column with the given header.
+    fn column(&self, name: &str) -> Option<&ColumnProfile> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Save the `FileProfile` to a JSON file
+    pub fn save_to_file(&self, file_path: &Path) -> Result<(), DataLintError> {
+        self.write_to(File::create(file_path)?)
+    }
+
+    /// Write the `FileProfile` in pretty JSON format to any writer, such as the standard output
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), DataLintError> {
+        let mut writer: BufWriter<W> = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(std::io::Error::from)?;
+        writeln!(writer)?;
+        Ok(writer.flush()?)
+    }
+}
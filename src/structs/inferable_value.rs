@@ -1,20 +1,35 @@
+use crate::enums::severity::Severity;
+use crate::structs::detection::Detection;
+use crate::structs::field_region::FieldRegion;
+
 /// This module defines the `InferableValue` struct, which represents a value that can be inferred
+/// along with the line where its record starts, the region of its field, the names of the unsafe patterns it matches and their highest severity.
+/// A value reported by a detector or not matching the type of its column in the schema is not inferred
+/// and holds its detection instead.
 #[non_exhaustive]
 pub struct InferableValue {
     pub value: String,
-    pub row_number: usize,
+    pub line: u32,
     pub column_index: usize,
+    pub region: Option<FieldRegion>, // Where the field lies in the file, when it is known
+    pub matched_patterns: Vec<String>,
+    pub severity: Severity,
+    pub detection: Option<Detection>,
 }
 
 impl InferableValue {
     /// Creates a new instance of `InferableValue`.
     #[inline]
     #[must_use]
-    pub const fn new(value: String, row_number: usize, column_index: usize) -> Self {
+    pub const fn new(value: String, line: u32, column_index: usize) -> Self {
         Self {
             value,
-            row_number,
+            line,
             column_index,
+            region: None,
+            matched_patterns: Vec::new(),
+            severity: Severity::Medium,
+            detection: None,
         }
     }
 }
@@ -22,28 +37,45 @@ impl InferableValue {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::enums::detection_source::DetectionSource;
 
     #[tokio::test]
     async fn test_batch_data() {
         let batch_data = InferableValue {
             value: "test".into(),
-            row_number: 1,
+            line: 2,
             column_index: 2,
+            region: Some(FieldRegion::new((2, 5), (3, 2))),
+            matched_patterns: vec![String::from("illegal_char_regex")],
+            severity: Severity::Low,
+            detection: None,
         };
         assert_eq!(batch_data.value, "test");
-        assert_eq!(batch_data.row_number, 1);
+        assert_eq!(batch_data.line, 2);
         assert_eq!(batch_data.column_index, 2);
+        assert_eq!(batch_data.region.unwrap().end_line, 3);
+        assert_eq!(batch_data.matched_patterns, ["illegal_char_regex"]);
+        assert_eq!(batch_data.severity, Severity::Low);
     }
 
     #[tokio::test]
     async fn test_batch_data_empty() {
         let batch_data = InferableValue {
             value: String::new(),
-            row_number: 0,
+            line: 0,
             column_index: 0,
+            region: None,
+            matched_patterns: Vec::new(),
+            severity: Severity::default(),
+            detection: Some(Detection::new(
+                DetectionSource::Schema,
+                vec![String::from("schema:integer")],
+                Severity::Medium,
+            )),
         };
         assert_eq!(batch_data.value, String::new());
-        assert_eq!(batch_data.row_number, 0);
+        assert_eq!(batch_data.line, 0);
         assert_eq!(batch_data.column_index, 0);
+        assert_eq!(batch_data.detection.unwrap().reasons, ["schema:integer"]);
     }
 }
@@ -0,0 +1,55 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::report_summary::ReportSummary;
+use crate::structs::structure_finding::StructureFinding;
+use crate::traits::report_writer::ReportWriter;
+use serde::Serialize;
+use std::io::{BufWriter, Write};
+
+/// Represents a line of a JSON Lines report, tagged with its type.
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum JsonLinesRecord<'a> {
+    Anomaly(&'a Anomaly),
+    Structure(&'a StructureFinding),
+    Summary(&'a ReportSummary),
+}
+
+/// Writes a JSON Lines report: one anomaly per line as it is found, then the structural findings
+/// and a summary record. The lines are buffered and written as the buffer fills, the report being flushed once complete.
+#[non_exhaustive]
+pub struct JsonLinesWriter {
+    writer: BufWriter<Box<dyn Write>>,
+}
+
+impl JsonLinesWriter {
+    /// Create a new `JsonLinesWriter` writing to the given writer.
+    #[inline]
+    #[must_use]
+    pub fn new(writer: Box<dyn Write>) -> Self {
+        Self {
+            writer: BufWriter::new(writer),
+        }
+    }
+
+    /// Write a record on its own line.
+    fn write_record(&mut self, record: &JsonLinesRecord) -> Result<(), DataLintError> {
+        serde_json::to_writer(&mut self.writer, record).map_err(std::io::Error::from)?;
+        Ok(writeln!(self.writer)?)
+    }
+}
+
+impl ReportWriter for JsonLinesWriter {
+    fn write_anomaly(&mut self, anomaly: Anomaly) -> Result<(), DataLintError> {
+        self.write_record(&JsonLinesRecord::Anomaly(&anomaly))
+    }
+
+    fn write_finding(&mut self, finding: StructureFinding) -> Result<(), DataLintError> {
+        self.write_record(&JsonLinesRecord::Structure(&finding))
+    }
+
+    fn finish(&mut self, summary: &ReportSummary) -> Result<(), DataLintError> {
+        self.write_record(&JsonLinesRecord::Summary(summary))?;
+        Ok(self.writer.flush()?)
+    }
+}
@@ -1,16 +1,28 @@
+use crate::enums::datalint_error::DataLintError;
 use crate::structs::anomaly::Anomaly;
+use crate::structs::column_stats::ColumnStats;
+use crate::structs::structure_finding::StructureFinding;
 
 use serde::{Deserialize, Serialize};
+use std::io::{BufWriter, Write};
+use std::path::Path;
 
-/// Represents the output of the JSON analysis, containing details about the analysed file,
-/// the AI and regex analysis scores, the time taken for the analysis, and a list of anomalies detected.
+/// Represents the output of the JSON analysis, containing details about the analysed file and its encoding,
+/// the AI and regex analysis scores, the time taken for the analysis, the statistics of each column,
+/// the structural problems of the file and a list of anomalies detected.
 #[derive(Serialize, Deserialize)]
 #[non_exhaustive]
 pub struct JsonOutput {
     pub analysed_file: String,
+    #[serde(default)]
+    pub encoding: String,
     pub ai_analyze: u32,
     pub regex_analyze: u32,
     pub time_ms: u128,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnStats>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub structure: Vec<StructureFinding>,
     pub anomalies: Vec<Anomaly>,
 }
 
@@ -27,16 +39,26 @@ impl JsonOutput {
     ) -> Self {
         Self {
             analysed_file,
+            encoding: String::new(),
             ai_analyze,
             regex_analyze,
             time_ms,
+            columns: Vec::new(),
+            structure: Vec::new(),
             anomalies,
         }
     }
 
     /// Save the `JsonOutput` to a file in pretty JSON format
-    pub fn save_to_file(&self, file_path: &str) -> std::io::Result<()> {
-        let json_data: String = serde_json::to_string_pretty(self)?;
-        std::fs::write(file_path, json_data)
+    pub fn save_to_file(&self, file_path: &Path) -> Result<(), DataLintError> {
+        self.write_to(std::fs::File::create(file_path)?)
+    }
+
+    /// Write the `JsonOutput` in pretty JSON format to any writer, such as the standard output
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), DataLintError> {
+        let mut writer: BufWriter<W> = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(std::io::Error::from)?;
+        writeln!(writer)?;
+        Ok(writer.flush()?)
     }
 }
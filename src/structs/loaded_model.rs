@@ -0,0 +1,434 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::column_stats::ColumnStats;
+use crate::structs::csv_file::CsvFile;
+use crate::structs::inferable_value::InferableValue;
+use crate::structs::lru_cache::LruCache;
+use crate::structs::model::Model;
+use crate::structs::rule_set::RuleSet;
+use crate::structs::scored_value::ScoredValue;
+use crate::structs::structure_finding::StructureFinding;
+use crate::structs::tokenizer::ModelTokenizer;
+use csv::StringRecord;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+use tch::{CModule, Device, Tensor};
+use tokenizers::{Encoding, Tokenizer};
+
+/// Represents a model loaded in memory with its device, tokenizer and rules.
+/// It is created once with `Model::load` and reused to analyse many files without reloading the model.
+/// A deferred instance only loads the model and the tokenizer once the first value has to be inferred.
+#[non_exhaustive]
+pub struct LoadedModel {
+    pub config: Model,
+    pub rule_set: RuleSet,
+    device: Device,
+    module: OnceLock<CModule>,
+    tokenizer: OnceLock<Tokenizer>,
+}
+
+impl LoadedModel {
+    /// Load the model on the best available device, the tokenizer and the rules, from the paths of the configuration.
+    pub fn new(config: Model) -> Result<Self, DataLintError> {
+        let loaded_model: Self = Self::deferred(config)?;
+        loaded_model.module()?;
+        loaded_model.tokenizer()?;
+        Ok(loaded_model)
+    }
+
+    /// Load the rules of the configuration, the model and the tokenizer being loaded on their first use.
+    pub(crate) fn deferred(config: Model) -> Result<Self, DataLintError> {
+        Ok(Self {
+            rule_set: config.rule_set()?,
+            config,
+            device: Device::cuda_if_available(),
+            module: OnceLock::new(),
+            tokenizer: OnceLock::new(),
+        })
+    }
+
+    /// Return the model, loading it on the device the first time.
+    fn module(&self) -> Result<&CModule, DataLintError> {
+        if let Some(module) = self.module.get() {
+            return Ok(module);
+        }
+
+        let mut module: CModule = CModule::load_on_device(&self.config.model_path, self.device)
+            .map_err(|e| {
+                DataLintError::Inference(format!(
+                    "Error loading model {}: {e}",
+                    self.config.model_path
+                ))
+            })?;
+        module.set_eval();
+        Ok(self.module.get_or_init(|| module))
+    }
+
+    /// Return the tokenizer, loading it the first time.
+    fn tokenizer(&self) -> Result<&Tokenizer, DataLintError> {
+        if let Some(tokenizer) = self.tokenizer.get() {
+            return Ok(tokenizer);
+        }
+
+        let tokenizer: Tokenizer = ModelTokenizer::from_config_file(&self.config.vocabulary_path)?;
+        Ok(self.tokenizer.get_or_init(|| tokenizer))
+    }
+
+    /// Analyse a CSV file and return a tuple containing the detected anomalies,
+    /// the number of AI analyses performed, and the number of regex analyses performed.
+    pub fn analyse(
+        &self,
+        csv_file_struct: &CsvFile,
+    ) -> Result<(Vec<Anomaly>, u32, u32), DataLintError> {
+        let mut anomalies: Vec<Anomaly> = Vec::new();
+
+        let (ai_analyze, regex_analyze, _, _): (u32, u32, Vec<ColumnStats>, Vec<StructureFinding>) =
+            self.analyse_streaming(csv_file_struct, |anomaly| {
+                anomalies.push(anomaly);
+                Ok(())
+            })?;
+
+        Ok((anomalies, ai_analyze, regex_analyze))
+    }
+
+    /// Analyse a CSV file chunk by chunk and pass each anomaly to `on_anomaly` as soon as its chunk is inferred.
+    /// The analysis stops at the first error returned by `on_anomaly`.
+    /// Every occurrence of an unsafe value is reported, the model inferring each distinct value only once
+    /// while it stays among the last `cache_size` values scored.
+    /// Return the number of AI analyses performed, the number of regex analyses performed, the statistics of each column
+    /// and the structural findings of the file.
+    pub fn analyse_streaming<F>(
+        &self,
+        csv_file_struct: &CsvFile,
+        mut on_anomaly: F,
+    ) -> Result<(u32, u32, Vec<ColumnStats>, Vec<StructureFinding>), DataLintError>
+    where
+        F: FnMut(Anomaly) -> Result<(), DataLintError>,
+    {
+        let mut regex_analyze: u32 = 0;
+        let mut ai_analyze: u32 = 0;
+        let mut column_stats: Vec<ColumnStats> = Vec::new();
+        let mut scored_values: LruCache<String, ScoredValue> =
+            LruCache::new(self.config.cache_size);
+        let headers: StringRecord = csv_file_struct.get_headers()?;
+
+        let structure_findings: Vec<StructureFinding> = csv_file_struct.stream_unsafe_values(
+            self.config.chunk_size,
+            self.config.cache_size,
+            &self.rule_set,
+            &self.config.schema,
+            &mut regex_analyze,
+            &mut column_stats,
+            |chunk, column_stats| {
+                self.analyse_chunk(
+                    &chunk,
+                    &headers,
+                    &mut scored_values,
+                    &mut ai_analyze,
+                    column_stats,
+                    &mut on_anomaly,
+                )
+            },
+        )?;
+
+        Ok((ai_analyze, regex_analyze, column_stats, structure_findings))
+    }
+
+    /// Infer the values of a chunk that were not scored yet and pass the anomalies found to `on_anomaly`,
+    /// one per occurrence of a value scored above the threshold of its column or not matching its type.
+    /// The scores and the anomalies are added to the statistics of their columns.
+    fn analyse_chunk<F>(
+        &self,
+        chunk: &[InferableValue],
+        headers: &StringRecord,
+        scored_values: &mut LruCache<String, ScoredValue>,
+        ai_analyze: &mut u32,
+        column_stats: &mut [ColumnStats],
+        on_anomaly: &mut F,
+    ) -> Result<(), DataLintError>
+    where
+        F: FnMut(Anomaly) -> Result<(), DataLintError>,
+    {
+        let chunk_scores: HashMap<&str, ScoredValue> =
+            self.score_chunk(chunk, scored_values, ai_analyze)?;
+
+        for data in chunk {
+            let mut stats: Option<&mut ColumnStats> = column_stats.get_mut(data.column_index);
+
+            let anomaly: Option<Anomaly> = if data.detection.is_some() {
+                self.config.detection_anomaly(data, headers)
+            } else {
+                chunk_scores
+                    .get(data.value.as_str())
+                    .and_then(|scored_value| {
+                        if let Some(stats) = stats.as_deref_mut() {
+                            stats.add_score(scored_value.score);
+                        }
+                        self.to_anomaly(data, scored_value, headers)
+                    })
+            };
+
+            if let Some(anomaly) = anomaly {
+                if let Some(stats) = stats {
+                    stats.anomalies += 1;
+                }
+                on_anomaly(anomaly)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the score of each distinct value of a chunk, except the detected values, taken from `scored_values`
+    /// or inferred and added to it. The scores of the chunk are returned apart, as the cache may not hold them all.
+    /// The model is not needed, nor loaded, when every value was already scored.
+    /// The tokens raising the score are only searched for the values that are an anomaly in at least one column.
+    fn score_chunk<'a>(
+        &self,
+        chunk: &'a [InferableValue],
+        scored_values: &mut LruCache<String, ScoredValue>,
+        ai_analyze: &mut u32,
+    ) -> Result<HashMap<&'a str, ScoredValue>, DataLintError> {
+        let mut chunk_scores: HashMap<&str, ScoredValue> = HashMap::new();
+        let mut new_values: Vec<&str> = Vec::new();
+        let mut seen_values: HashSet<&str> = HashSet::new();
+
+        for data in chunk.iter().filter(|data| data.detection.is_none()) {
+            if !seen_values.insert(&data.value) {
+                continue;
+            }
+            match scored_values.get(&data.value) {
+                Some(scored_value) => {
+                    chunk_scores.insert(&data.value, scored_value.clone());
+                }
+                None => new_values.push(&data.value),
+            }
+        }
+
+        if new_values.is_empty() {
+            return Ok(chunk_scores);
+        }
+
+        let batch_data: Vec<InferableValue> = new_values
+            .iter()
+            .map(|value| InferableValue::new(String::from(*value), 0, 0))
+            .collect();
+        let (encodings, predictions): (Vec<Encoding>, Tensor) = self.predict(&batch_data)?;
+        let lowest_threshold: f64 = self.config.lowest_threshold();
+
+        // Get prediction scores as a 1D vector
+        let scores = predictions.select(1, 1).iter::<f64>()?;
+
+        for ((value, encoding), score) in new_values.into_iter().zip(&encodings).zip(scores) {
+            *ai_analyze += 1;
+
+            let top_tokens: Vec<String> = if score > lowest_threshold {
+                self.top_tokens(encoding, score)?
+            } else {
+                Vec::new()
+            };
+            let scored_value: ScoredValue = ScoredValue::new(score, top_tokens);
+            scored_values.insert(String::from(value), scored_value.clone());
+            chunk_scores.insert(value, scored_value);
+        }
+
+        Ok(chunk_scores)
+    }
+
+    /// Return the score given by the model to each value, without applying the threshold.
+    pub fn score_values(&self, values: &[String]) -> Result<Vec<f64>, DataLintError> {
+        if values.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let batch_data: Vec<InferableValue> = values
+            .iter()
+            .map(|value| InferableValue::new(value.clone(), 0, 0))
+            .collect();
+
+        let (_, predictions): (Vec<Encoding>, Tensor) = self.predict(&batch_data)?;
+        Ok(predictions.select(1, 1).iter::<f64>()?.collect())
+    }
+
+    /// Return the score given by the model to a value and the tokens that raise it the most.
+    pub fn explain_value(&self, value: &str) -> Result<(f64, Vec<String>), DataLintError> {
+        let batch_data: [InferableValue; 1] = [InferableValue::new(String::from(value), 0, 0)];
+        let (encodings, predictions): (Vec<Encoding>, Tensor) = self.predict(&batch_data)?;
+
+        let score: f64 = predictions
+            .select(1, 1)
+            .iter::<f64>()?
+            .next()
+            .unwrap_or_default();
+
+        match encodings.first() {
+            Some(encoding) => Ok((score, self.top_tokens(encoding, score)?)),
+            None => Ok((score, Vec::new())),
+        }
+    }
+
+    /// Tokenize a batch of values and run the inference on it, returning the encodings with the predictions.
+    fn predict(
+        &self,
+        batch_data: &[InferableValue],
+    ) -> Result<(Vec<Encoding>, Tensor), DataLintError> {
+        let (encodings, max_seq_length) =
+            ModelTokenizer::encode_words(self.tokenizer()?, batch_data)?;
+        let predictions: Tensor = self.run_sigmoid_inference_batched(&encodings, max_seq_length)?;
+        Ok((encodings, predictions))
+    }
+
+    /// Return the tokens of an encoded value that raise its score the most, by occlusion:
+    /// each token is masked in turn and its attribution is the drop of the score without it.
+    fn top_tokens(&self, encoding: &Encoding, score: f64) -> Result<Vec<String>, DataLintError> {
+        const TOP_TOKENS: usize = 3;
+
+        let (ids, seq_length) = ModelTokenizer::ids_to_vector(encoding);
+        let positions: Vec<usize> = encoding
+            .get_special_tokens_mask()
+            .iter()
+            .enumerate()
+            .filter(|&(_, &special)| special == 0)
+            .map(|(position, _)| position)
+            .collect();
+
+        if positions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut occluded_ids: Vec<i64> = Vec::with_capacity(positions.len() * ids.len());
+        let mut occluded_masks: Vec<i64> = Vec::with_capacity(positions.len() * ids.len());
+        for &position in &positions {
+            for (index, &id) in ids.iter().enumerate() {
+                let masked: bool = index == position;
+                occluded_ids.push(if masked {
+                    ModelTokenizer::PAD_TOKEN_ID
+                } else {
+                    id
+                });
+                occluded_masks.push(i64::from(!masked));
+            }
+        }
+
+        let batch_size: i64 = i64::try_from(positions.len()).unwrap_or(0);
+        let input_ids: Tensor = Tensor::from_slice(&occluded_ids)
+            .view((batch_size, seq_length))
+            .to_device(self.device);
+        let attention_mask: Tensor = Tensor::from_slice(&occluded_masks)
+            .view((batch_size, seq_length))
+            .to_device(self.device);
+
+        let occluded_scores = self
+            .forward(input_ids, attention_mask)?
+            .sigmoid()
+            .select(1, 1)
+            .iter::<f64>()?;
+
+        let mut attributions: Vec<(f64, usize)> = occluded_scores
+            .zip(positions)
+            .map(|(occluded_score, position)| (score - occluded_score, position))
+            .filter(|&(attribution, _)| attribution > 0.0)
+            .collect();
+        attributions.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let tokens: &[String] = encoding.get_tokens();
+        Ok(attributions
+            .into_iter()
+            .take(TOP_TOKENS)
+            .filter_map(|(_, position)| tokens.get(position).cloned())
+            .collect())
+    }
+
+    /// Forward pass through the model with input IDs and attention mask.
+    fn forward(&self, input_ids: Tensor, attention_mask: Tensor) -> Result<Tensor, DataLintError> {
+        let module: &CModule = self.module()?;
+        tch::no_grad(|| module.forward_ts(&[input_ids, attention_mask]))
+            .map_err(|e| DataLintError::Inference(format!("Error during model inference: {e}")))
+    }
+
+    /// Execute the inference in batches using sigmoid activation.
+    fn run_sigmoid_inference_batched(
+        &self,
+        encodings: &[Encoding],
+        max_seq_length: i64,
+    ) -> Result<Tensor, DataLintError> {
+        const MAX_BATCH_SIZE: usize = 32;
+
+        // Fast path for small batches (optional performance boost)
+        if encodings.len() < 5000 {
+            return Ok(self
+                .run_single_batch_inference(encodings, max_seq_length)?
+                .sigmoid());
+        }
+
+        let mut all_outputs: Vec<Tensor> = Vec::new();
+
+        for batch in encodings.chunks(MAX_BATCH_SIZE) {
+            let output: Tensor = self.run_single_batch_inference(batch, max_seq_length)?;
+            all_outputs.push(output);
+        }
+
+        Ok(Tensor::cat(&all_outputs, 0).sigmoid())
+    }
+
+    /// Run inference for a single batch of encodings.
+    fn run_single_batch_inference(
+        &self,
+        batch: &[Encoding],
+        max_seq_length: i64,
+    ) -> Result<Tensor, DataLintError> {
+        let (padded_ids, attention_masks) = ModelTokenizer::build_tokens(batch, max_seq_length);
+        let batch_size: i64 = i64::try_from(batch.len()).unwrap_or(0);
+
+        let input_ids: Tensor = Tensor::from_slice(&padded_ids)
+            .view((batch_size, max_seq_length))
+            .to_device(self.device);
+
+        let attention_mask: Tensor = Tensor::from_slice(&attention_masks)
+            .view((batch_size, max_seq_length))
+            .to_device(self.device);
+
+        self.forward(input_ids, attention_mask)
+    }
+
+    /// Return the anomaly of a value if its score exceeds the threshold of its column.
+    /// The reasons of an anomaly are the unsafe patterns its value matches, then the tokens that raise its score the most.
+    fn to_anomaly(
+        &self,
+        data: &InferableValue,
+        scored_value: &ScoredValue,
+        headers: &StringRecord,
+    ) -> Option<Anomaly> {
+        let column_name: &str = headers.get(data.column_index).unwrap_or("unknown");
+        let threshold: f64 = self.config.threshold_for(column_name);
+
+        // Check if the score exceeds the threshold of the column
+        if scored_value.score <= threshold {
+            return None;
+        }
+
+        let mut anomaly: Anomaly = Anomaly::new(
+            data.value.clone(),
+            column_name.into(),
+            data.line,
+            scored_value.score as f32,
+            threshold as f32,
+        );
+        anomaly.column_index = data.column_index;
+        anomaly.region = data.region;
+        anomaly.reasons = Self::reasons(data, &scored_value.top_tokens);
+        anomaly.severity = data.severity;
+        Some(anomaly)
+    }
+
+    /// Return the reasons of an anomaly: `pattern:<name>` for each matching unsafe pattern,
+    /// then `model` and `token:<token>` for each token raising the score of the model the most.
+    fn reasons(data: &InferableValue, top_tokens: &[String]) -> Vec<String> {
+        data.matched_patterns
+            .iter()
+            .map(|pattern| format!("pattern:{pattern}"))
+            .chain(std::iter::once(String::from("model")))
+            .chain(top_tokens.iter().map(|token| format!("token:{token}")))
+            .collect()
+    }
+}
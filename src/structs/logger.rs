@@ -2,12 +2,14 @@ use crate::enums::color::Color;
 use crate::enums::log_level::LogLevel;
 use std::fs::File;
 use std::io::{BufWriter, Write};
-use std::sync::{Mutex, MutexGuard};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 
 /// Logger struct to handle logging messages to a file
+/// Messages are only printed when the log file cannot be opened, for instance in a read-only directory
 #[non_exhaustive]
 pub struct Logger {
-    log_file: File,
+    log_file: Option<File>,
 }
 
 impl Logger {
@@ -15,25 +17,30 @@ impl Logger {
     #[inline]
     #[must_use]
     fn new() -> Self {
-        let log_file: File = std::fs::OpenOptions::new()
+        let log_file: Option<File> = std::fs::OpenOptions::new()
             .create(true)
             .append(true)
             .open("DataLint.log")
-            .unwrap();
+            .ok();
 
         Self { log_file }
     }
 
     /// Log a message with the specified log level
     fn log(&self, log_level: &LogLevel, message: &str) {
-        let mut log_writer: BufWriter<&File> = BufWriter::new(&self.log_file);
-        writeln!(
+        let Some(log_file) = &self.log_file else {
+            return;
+        };
+
+        let mut log_writer: BufWriter<&File> = BufWriter::new(log_file);
+        if let Err(e) = writeln!(
             log_writer,
             "[{:?}] {} {message}",
             chrono::Local::now(),
             log_level.as_str()
-        )
-        .unwrap_or_else(|_| panic!("Error writing to log file"));
+        ) {
+            print_message(&format!("Error writing to log file: {e}"), &LogLevel::Error);
+        }
     }
 }
 
@@ -41,21 +48,31 @@ impl Logger {
 pub static LOGGER: std::sync::LazyLock<Mutex<Logger>> =
     std::sync::LazyLock::new(|| Mutex::new(Logger::new()));
 
+/// When set, informational messages are no longer printed to the terminal
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable the printing of informational messages
+pub fn set_quiet(quiet: bool) {
+    QUIET.store(quiet, Ordering::Relaxed);
+}
+
 /// Static logger instance
 pub fn log_and_print_message(message: &str, log_level: &LogLevel) {
     print_message(message, log_level);
-    let logger: MutexGuard<Logger> = LOGGER.lock().unwrap();
+    let logger: MutexGuard<Logger> = LOGGER.lock().unwrap_or_else(PoisonError::into_inner);
     logger.log(log_level, message);
 }
 
 pub fn log_message(message: &str, log_level: &LogLevel) {
-    let logger: MutexGuard<Logger> = LOGGER.lock().unwrap();
+    let logger: MutexGuard<Logger> = LOGGER.lock().unwrap_or_else(PoisonError::into_inner);
     logger.log(log_level, message);
 }
 
 pub fn print_message(message: &str, log_level: &LogLevel) {
     match log_level {
         LogLevel::Error => eprintln!("{}[ERROR] {message}{}", Color::Red, Color::Reset),
+        LogLevel::Warning => eprintln!("{}[WARNING] {message}{}", Color::Yellow, Color::Reset),
+        LogLevel::Info if QUIET.load(Ordering::Relaxed) => {}
         LogLevel::Info => println!("{}[INFO] {message}{}", Color::Green, Color::Reset),
     }
 }
@@ -0,0 +1,103 @@
+use std::borrow::Borrow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+
+/// Represents a cache holding at most `capacity` entries, evicting the least recently used one when it is full,
+/// so that the values remembered while a file is scanned do not grow with its number of distinct values.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct LruCache<K, V> {
+    capacity: usize,
+    entries: HashMap<K, (V, u64)>, // Value and last use of each key
+    recency: BTreeMap<u64, K>,     // Keys by last use, the least recently used first
+    clock: u64,
+}
+
+impl<K: Clone + Eq + Hash, V> LruCache<K, V> {
+    /// Create an empty cache holding at most `capacity` entries, and at least one.
+    #[inline]
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+        }
+    }
+
+    /// Return the value of the key and mark it as the most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.clock += 1;
+        let (value, last_use) = self.entries.get_mut(key)?;
+        if let Some(key) = self.recency.remove(last_use) {
+            self.recency.insert(self.clock, key);
+        }
+        *last_use = self.clock;
+        Some(value)
+    }
+
+    /// Insert the value of the key as the most recently used, evicting the least recently used entry when full.
+    pub fn insert(&mut self, key: K, value: V) {
+        self.clock += 1;
+        if let Some((_, last_use)) = self.entries.remove(&key) {
+            self.recency.remove(&last_use);
+        } else if self.entries.len() >= self.capacity
+            && let Some((_, evicted)) = self.recency.pop_first()
+        {
+            self.entries.remove(&evicted);
+        }
+
+        self.recency.insert(self.clock, key.clone());
+        self.entries.insert(key, (value, self.clock));
+    }
+
+    /// Return the number of entries of the cache.
+    #[inline]
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Return true if the cache has no entry.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_lru_cache_eviction() {
+        let mut cache: LruCache<String, u32> = LruCache::new(2);
+        cache.insert(String::from("a"), 1);
+        cache.insert(String::from("b"), 2);
+        assert_eq!(cache.get("a"), Some(&1));
+
+        // "b" is the least recently used entry since "a" was read
+        cache.insert(String::from("c"), 3);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("b"), None);
+        assert_eq!(cache.get("a"), Some(&1));
+        assert_eq!(cache.get("c"), Some(&3));
+
+        cache.insert(String::from("c"), 4);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("c"), Some(&4));
+
+        let mut single: LruCache<String, u32> = LruCache::new(0);
+        assert!(single.is_empty());
+        single.insert(String::from("a"), 1);
+        single.insert(String::from("b"), 2);
+        assert_eq!(single.len(), 1);
+        assert_eq!(single.get("b"), Some(&2));
+    }
+}
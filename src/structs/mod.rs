@@ -1,7 +1,38 @@
 pub mod anomaly;
+pub mod batch_summary;
+pub mod buffered_report_writer;
+pub mod cli;
+pub mod column_profile;
+pub mod column_profiler;
+pub mod column_stats;
 pub mod csv_file;
+pub mod csv_report_writer;
+pub mod csv_splitter;
+pub mod detection;
+pub mod field_region;
+pub mod file_profile;
 pub mod inferable_value;
+pub mod json_lines_writer;
 pub mod json_output;
+pub mod loaded_model;
 pub mod logger;
+pub mod lru_cache;
 pub mod model;
-pub mod tokenizer;
\ No newline at end of file
+pub mod pattern_detector;
+pub mod report_summary;
+pub mod rule;
+pub mod rule_matches;
+pub mod rule_set;
+pub mod rules_file;
+pub mod sarif_output;
+pub mod schema;
+pub mod schema_drift;
+pub mod scored_value;
+pub mod sniffer;
+pub mod sql_injection_detector;
+pub mod sql_token;
+pub mod structure_checker;
+pub mod structure_finding;
+pub mod tokenizer;
+pub mod unicode_detector;
+pub mod utf8_fallback_reader;
@@ -1,183 +1,224 @@
-use crate::enums::log_level::LogLevel;
+use crate::enums::datalint_error::DataLintError;
 use crate::structs::anomaly::Anomaly;
+use crate::structs::column_stats::ColumnStats;
 use crate::structs::csv_file::CsvFile;
+use crate::structs::detection::Detection;
 use crate::structs::inferable_value::InferableValue;
-use crate::structs::logger::print_message;
-use crate::structs::tokenizer::ModelTokenizer;
+use crate::structs::loaded_model::LoadedModel;
+use crate::structs::rule_set::RuleSet;
+use crate::structs::schema::Schema;
+use crate::structs::structure_finding::StructureFinding;
 use csv::StringRecord;
-use serde::Deserialize;
-use std::error::Error;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs::File;
-use tch::{CModule, Device, Tensor};
-use tokenizers::{Encoding, Tokenizer};
+use std::path::Path;
 
 /// Represents the model configuration for the anomaly detection system.
 /// It contains the paths to the model and vocabulary files.
 /// The model is used for inference, while the vocabulary is used for tokenization.
 /// The model is expected to be a PyTorch model, and the vocabulary is expected to be a tokenizer configuration file.
-#[derive(Deserialize)]
+/// Values scored above the threshold are reported as anomalies, the threshold can be overridden per column header.
+/// The optional rules file adds allow and deny rules to the built-in ones, or replaces them.
+/// The optional schema gives the type of some columns, whose values are checked against it instead of the model.
+#[derive(Clone, Serialize, Deserialize)]
 pub struct Model {
     pub model_path: String,
     pub vocabulary_path: String,
+    #[serde(default = "Model::default_threshold")]
+    pub threshold: f64,
+    #[serde(default)]
+    pub column_thresholds: HashMap<String, f64>,
+    #[serde(default = "Model::default_chunk_size")]
+    pub chunk_size: usize,
+    #[serde(default = "Model::default_cache_size")]
+    pub cache_size: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rules_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Schema::is_empty")]
+    pub schema: Schema,
 }
 
 impl Model {
-    /// Load the model configuration from a JSON file and return a Model instance.
-    pub fn from_config_file(json_path: &str) -> Result<Self, Box<dyn Error>> {
-        let json_file: File = File::open(json_path)?;
-        let model = serde_json::from_reader(json_file).unwrap_or_else(|e| {
-            print_message(
-                &format!("Error reading model configuration from JSON: {e}"),
-                &LogLevel::Error,
-            );
-            std::process::exit(1);
-        });
-
-        Ok(model)
+    /// Default score above which a value is reported as an anomaly.
+    pub const DEFAULT_THRESHOLD: f64 = 0.8;
+
+    /// Return the default threshold, used when the configuration does not define one.
+    #[inline]
+    #[must_use]
+    const fn default_threshold() -> f64 {
+        Self::DEFAULT_THRESHOLD
     }
 
-    /// Init the model, device, and tokenizer based on the model path and vocabulary path.
-    fn init_model(&self) -> Result<(CModule, Device, Tokenizer), Box<dyn Error>> {
-        let device: Device = Device::cuda_if_available();
-        let model: CModule =
-            CModule::load_on_device(&self.model_path, device).unwrap_or_else(|e| {
-                print_message(&format!("Error loading model: {e}"), &LogLevel::Error);
-                std::process::exit(1);
-            });
-        let tokenizer: Tokenizer = ModelTokenizer::from_config_file(&self.vocabulary_path)?;
-        Ok((model, device, tokenizer))
+    /// Default number of values tokenized and inferred at once while streaming a file.
+    pub const DEFAULT_CHUNK_SIZE: usize = 4096;
+
+    /// Return the default chunk size, used when the configuration does not define one.
+    #[inline]
+    #[must_use]
+    const fn default_chunk_size() -> usize {
+        Self::DEFAULT_CHUNK_SIZE
     }
 
-    /// Analyse a CSV file and return a tuple containing the detected anomalies,
-    /// the number of AI analyses performed, and the number of regex analyses performed.
-    pub fn analyse_file(
-        &self,
-        csv_file_struct: &CsvFile,
-    ) -> Result<(Vec<Anomaly>, u32, u32), Box<dyn Error>> {
-        let mut regex_analyze: u32 = 0;
-        let mut ai_analyze: u32 = 0;
+    /// Default number of distinct values whose rule matches and scores are remembered while streaming a file.
+    pub const DEFAULT_CACHE_SIZE: usize = 100_000;
 
-        let batch_data: Vec<InferableValue> =
-            csv_file_struct.collect_unsafe_value(csv_file_struct, &mut regex_analyze)?;
+    /// Return the default cache size, used when the configuration does not define one.
+    #[inline]
+    #[must_use]
+    const fn default_cache_size() -> usize {
+        Self::DEFAULT_CACHE_SIZE
+    }
 
-        if batch_data.is_empty() {
-            return Ok((Vec::new(), ai_analyze, regex_analyze));
-        }
+    /// Load the model configuration from a JSON file and return a Model instance.
+    /// Relative model, vocabulary and rules paths are resolved against the directory of the configuration file.
+    pub fn from_config_file(json_path: impl AsRef<Path>) -> Result<Self, DataLintError> {
+        let json_path: &Path = json_path.as_ref();
+        let json_file: File = File::open(json_path)?;
+        let mut model: Self = serde_json::from_reader(json_file).map_err(|e| {
+            DataLintError::Config(format!(
+                "Error reading model configuration from {}: {e}",
+                json_path.display()
+            ))
+        })?;
+        model.check_thresholds()?;
+
+        let config_dir: &Path = json_path.parent().unwrap_or_else(|| Path::new(""));
+        model.model_path = Self::resolve_path(config_dir, &model.model_path);
+        model.vocabulary_path = Self::resolve_path(config_dir, &model.vocabulary_path);
+        model.rules_path = model
+            .rules_path
+            .map(|rules_path| Self::resolve_path(config_dir, &rules_path));
 
-        let (mut model, device, tokenizer): (CModule, Device, Tokenizer) = self.init_model()?;
+        Ok(model)
+    }
 
-        let (encodings, max_seq_length) = ModelTokenizer::encode_words(&tokenizer, &batch_data);
+    /// Check that the global threshold and the column thresholds lie between 0 and 1.
+    fn check_thresholds(&self) -> Result<(), DataLintError> {
+        let mut thresholds: Vec<(&str, f64)> = vec![("the configuration", self.threshold)];
+        thresholds.extend(
+            self.column_thresholds
+                .iter()
+                .map(|(column, threshold)| (column.as_str(), *threshold)),
+        );
 
-        let predictions: Tensor =
-            Self::run_sigmoid_inference_batched(&encodings, max_seq_length, &mut model, device);
+        match thresholds
+            .into_iter()
+            .find(|(_, threshold)| !(0.0..=1.0).contains(threshold))
+        {
+            Some((name, threshold)) => Err(DataLintError::Config(format!(
+                "Threshold of {name} must be between 0 and 1, got {threshold}"
+            ))),
+            None => Ok(()),
+        }
+    }
 
-        let anomalies: Vec<Anomaly> = Self::process_output(
-            &batch_data,
-            &predictions,
-            &csv_file_struct.get_headers()?,
-            &mut ai_analyze,
-        );
+    /// Return the threshold applied to the given column, falling back to the global threshold.
+    #[inline]
+    #[must_use]
+    pub fn threshold_for(&self, column_name: &str) -> f64 {
+        self.column_thresholds
+            .get(column_name)
+            .copied()
+            .unwrap_or(self.threshold)
+    }
 
-        Ok((anomalies, ai_analyze, regex_analyze))
+    /// Return the rules of the rules file of the configuration, or the built-in rules when it does not define one.
+    pub fn rule_set(&self) -> Result<RuleSet, DataLintError> {
+        self.rules_path
+            .as_ref()
+            .map_or_else(|| Ok(RuleSet::default()), RuleSet::from_file)
     }
 
-    /// Forward pass through the model with input IDs and attention mask.
-    fn forward(model: &CModule, input_ids: Tensor, attention_mask: Tensor) -> Tensor {
-        let output: Tensor = tch::no_grad(|| {
-            model
-                .forward_ts(&[input_ids, attention_mask])
-                .unwrap_or_else(|e| {
-                    print_message(
-                        &format!("Error during model inference: {e}"),
-                        &LogLevel::Error,
-                    );
-                    std::process::exit(1);
-                })
-        });
-
-        output
+    /// Return the lowest threshold applied to any column, under which a value is never an anomaly.
+    #[inline]
+    #[must_use]
+    pub fn lowest_threshold(&self) -> f64 {
+        self.column_thresholds
+            .values()
+            .copied()
+            .fold(self.threshold, f64::min)
     }
 
-    /// Execute the inference in batches using sigmoid activation.
-    fn run_sigmoid_inference_batched(
-        encodings: &[Encoding],
-        max_seq_length: i64,
-        model: &mut CModule,
-        device: Device,
-    ) -> Tensor {
-        const MAX_BATCH_SIZE: usize = 32;
-        model.set_eval();
-
-        // Fast path for small batches (optional performance boost)
-        if encodings.len() < 5000 {
-            return Self::run_single_batch_inference(encodings, max_seq_length, model, device)
-                .sigmoid();
-        }
+    /// Resolve a path relative to the given directory, absolute paths are returned unchanged.
+    #[inline]
+    #[must_use]
+    fn resolve_path(base_dir: &Path, path: &str) -> String {
+        base_dir.join(path).to_string_lossy().into_owned()
+    }
 
-        let mut all_outputs: Vec<Tensor> = Vec::new();
+    /// Load the model and the tokenizer in memory, to reuse them across many files.
+    pub fn load(&self) -> Result<LoadedModel, DataLintError> {
+        LoadedModel::new(self.clone())
+    }
 
-        for batch in encodings.chunks(MAX_BATCH_SIZE) {
-            let output: Tensor =
-                Self::run_single_batch_inference(batch, max_seq_length, model, device);
-            all_outputs.push(output);
-        }
+    /// Analyse a CSV file and return a tuple containing the detected anomalies,
+    /// the number of AI analyses performed, and the number of regex analyses performed.
+    /// The model is loaded for this file only, use `Model::load` to analyse many files.
+    pub fn analyse_file(
+        &self,
+        csv_file_struct: &CsvFile,
+    ) -> Result<(Vec<Anomaly>, u32, u32), DataLintError> {
+        let mut anomalies: Vec<Anomaly> = Vec::new();
+
+        let (ai_analyze, regex_analyze, _, _): (u32, u32, Vec<ColumnStats>, Vec<StructureFinding>) =
+            self.analyse_file_streaming(csv_file_struct, |anomaly| {
+                anomalies.push(anomaly);
+                Ok(())
+            })?;
 
-        Tensor::cat(&all_outputs, 0).sigmoid()
+        Ok((anomalies, ai_analyze, regex_analyze))
     }
 
-    /// Run inference for a single batch of encodings.
-    fn run_single_batch_inference(
-        batch: &[Encoding],
-        max_seq_length: i64,
-        model: &CModule,
-        device: Device,
-    ) -> Tensor {
-        let (padded_ids, attention_masks) = ModelTokenizer::build_tokens(batch, max_seq_length);
-        let batch_size: i64 = i64::try_from(batch.len()).unwrap_or(0);
-
-        let input_ids: Tensor = Tensor::from_slice(&padded_ids)
-            .view((batch_size, max_seq_length))
-            .to_device(device);
-
-        let attention_mask: Tensor = Tensor::from_slice(&attention_masks)
-            .view((batch_size, max_seq_length))
-            .to_device(device);
-
-        Self::forward(model, input_ids, attention_mask)
+    /// Analyse a CSV file chunk by chunk and pass each anomaly to `on_anomaly` as soon as its chunk is inferred.
+    /// The analysis stops at the first error returned by `on_anomaly`.
+    /// Return the number of AI analyses performed, the number of regex analyses performed, the statistics of each column
+    /// and the structural findings of the file.
+    /// The model is only loaded once the first value to infer is found, see `LoadedModel::analyse_streaming`.
+    pub fn analyse_file_streaming<F>(
+        &self,
+        csv_file_struct: &CsvFile,
+        on_anomaly: F,
+    ) -> Result<(u32, u32, Vec<ColumnStats>, Vec<StructureFinding>), DataLintError>
+    where
+        F: FnMut(Anomaly) -> Result<(), DataLintError>,
+    {
+        LoadedModel::deferred(self.clone())?.analyse_streaming(csv_file_struct, on_anomaly)
     }
 
-    /// Extract anomalies from the model's predictions and batch data.
-    fn process_output(
-        batch_data: &[InferableValue],
-        predictions: &Tensor,
+    /// Return the anomaly of a value reported by a detector or not matching the type of its column in the schema.
+    /// Its score and threshold are the ones of the detection, as it is reported whatever the score the model would
+    /// give it and whatever the threshold of its column.
+    #[must_use]
+    pub fn detection_anomaly(
+        &self,
+        data: &InferableValue,
         headers: &StringRecord,
-        ai_analyze: &mut u32,
-    ) -> Vec<Anomaly> {
-        const THRESHOLD: f64 = 0.8;
-        let mut anomalies: Vec<Anomaly> = Vec::new();
+    ) -> Option<Anomaly> {
+        let detection: &Detection = data.detection.as_ref()?;
+        let column_name: &str = headers.get(data.column_index).unwrap_or("unknown");
+
+        let mut anomaly: Anomaly = Anomaly::new(
+            data.value.clone(),
+            column_name.into(),
+            data.line,
+            detection.score as f32,
+            detection.threshold as f32,
+        );
+        anomaly.column_index = data.column_index;
+        anomaly.region = data.region;
+        anomaly.source = detection.source;
+        anomaly.reasons = detection.reasons.clone();
+        anomaly.severity = detection.severity;
+        Some(anomaly)
+    }
 
-        // Get prediction scores as a 1D vector
-        let scores = predictions.select(1, 1).iter::<f64>().unwrap();
-
-        for (i, score) in scores.enumerate() {
-            *ai_analyze += 1;
-
-            // Check if the score exceeds the threshold and if the corresponding data exists
-            if score > THRESHOLD && let Some(data) = batch_data.get(i)
-            {
-                let column_name: String =
-                    headers.get(data.column_index).unwrap_or("unknown").into();
-                let row_number: u32 = u32::try_from(data.row_number + 2).unwrap_or(u32::MAX);
-
-                anomalies.push(Anomaly::new(
-                    data.value.clone(),
-                    column_name,
-                    row_number,
-                    score as f32,
-                ));
-            }
+    /// Return the score given by the model to each value, without applying the threshold.
+    pub fn score_values(&self, values: &[String]) -> Result<Vec<f64>, DataLintError> {
+        if values.is_empty() {
+            return Ok(Vec::new());
         }
 
-        anomalies
+        self.load()?.score_values(values)
     }
 }
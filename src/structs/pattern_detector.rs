@@ -0,0 +1,192 @@
+use crate::enums::detection_source::DetectionSource;
+use crate::enums::severity::Severity;
+use crate::structs::detection::Detection;
+use crate::traits::detector::Detector;
+use crate::utils::decode::decode_payload;
+use crate::utils::regex::{
+    command_injection_patterns, formula_injection_patterns, ldap_injection_patterns,
+    path_traversal_patterns, xpath_injection_patterns, xss_payload_patterns,
+};
+use regex::RegexSet;
+
+/// Represents a detector reporting the values matching named patterns, each with its own severity.
+/// The reasons of a detection are the names of the matching patterns, prefixed by the category of the detector.
+/// A detector may decode the values before matching them, to see through the encodings hiding a payload.
+#[non_exhaustive]
+pub struct PatternDetector {
+    source: DetectionSource,
+    names: Vec<(&'static str, Severity)>,
+    regex_set: RegexSet,
+    decode: Option<fn(&str) -> String>,
+}
+
+impl PatternDetector {
+    /// Create a detector of the given category from built-in names, patterns and severities.
+    ///
+    /// # Panics
+    /// Panics if a pattern is not a valid regex, the built-in patterns being checked by the tests.
+    #[must_use]
+    pub fn new(
+        source: DetectionSource,
+        patterns: &[(&'static str, &'static str, Severity)],
+    ) -> Self {
+        Self {
+            source,
+            names: patterns
+                .iter()
+                .map(|&(name, _, severity)| (name, severity))
+                .collect(),
+            regex_set: RegexSet::new(patterns.iter().map(|(_, pattern, _)| pattern))
+                .expect("The built-in patterns are valid"),
+            decode: None,
+        }
+    }
+
+    /// Decode the values with the given function before matching them.
+    #[inline]
+    #[must_use]
+    pub fn with_decoder(mut self, decode: fn(&str) -> String) -> Self {
+        self.decode = Some(decode);
+        self
+    }
+
+    /// Create the detector of the values run as formulas by spreadsheets.
+    #[must_use]
+    pub fn formula() -> Self {
+        Self::new(DetectionSource::Formula, &formula_injection_patterns())
+    }
+
+    /// Create the detector of the HTML and JavaScript payloads, decoding their HTML entities,
+    /// URL encoding and JavaScript escapes first.
+    #[must_use]
+    pub fn xss() -> Self {
+        Self::new(DetectionSource::Xss, &xss_payload_patterns()).with_decoder(decode_payload)
+    }
+
+    /// Create the detector of the shell commands chained or substituted into a value.
+    #[must_use]
+    pub fn command() -> Self {
+        Self::new(DetectionSource::Command, &command_injection_patterns())
+    }
+
+    /// Create the detector of the paths climbing out of their directory, even encoded, and of the NUL bytes.
+    #[must_use]
+    pub fn path_traversal() -> Self {
+        Self::new(DetectionSource::PathTraversal, &path_traversal_patterns())
+    }
+
+    /// Create the detector of the fragments breaking out of an LDAP filter.
+    #[must_use]
+    pub fn ldap() -> Self {
+        Self::new(DetectionSource::Ldap, &ldap_injection_patterns())
+    }
+
+    /// Create the detector of the fragments breaking out of an XPath predicate.
+    #[must_use]
+    pub fn xpath() -> Self {
+        Self::new(DetectionSource::Xpath, &xpath_injection_patterns())
+    }
+}
+
+impl Detector for PatternDetector {
+    fn detect(&self, value: &str) -> Option<Detection> {
+        let decoded: String;
+        let value: &str = match self.decode {
+            Some(decode) => {
+                decoded = decode(value);
+                &decoded
+            }
+            None => value,
+        };
+
+        let matched: Vec<(&str, Severity)> = self
+            .regex_set
+            .matches(value)
+            .iter()
+            .filter_map(|index| self.names.get(index).copied())
+            .collect();
+
+        let severity: Severity = matched.iter().map(|&(_, severity)| severity).max()?;
+        let reasons: Vec<String> = matched
+            .iter()
+            .map(|(name, _)| format!("{}:{name}", self.source.as_str()))
+            .collect();
+
+        Some(Detection::new(self.source, reasons, severity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_formula_detector() {
+        let detector: PatternDetector = PatternDetector::formula();
+
+        let detection: Detection = detector.detect("=HYPERLINK(\"http://x.io\")").unwrap();
+        assert_eq!(detection.source, DetectionSource::Formula);
+        assert_eq!(
+            detection.reasons,
+            ["formula:exfiltration_function", "formula:function_call"]
+        );
+        assert_eq!(detection.severity, Severity::High);
+
+        assert_eq!(
+            detector.detect("=SUM(A1:A3)").unwrap().severity,
+            Severity::Medium
+        );
+        assert!(detector.detect("-42").is_none());
+        assert!(detector.detect("Hello world").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_xss_detector() {
+        let detector: PatternDetector = PatternDetector::xss();
+
+        let detection: Detection = detector
+            .detect("&lt;img src=x &#111;nerror=alert(1)&gt;")
+            .unwrap();
+        assert_eq!(detection.source, DetectionSource::Xss);
+        assert_eq!(detection.reasons, ["xss:event_handler"]);
+        assert_eq!(detection.severity, Severity::High);
+
+        assert_eq!(
+            detector
+                .detect("%3Cscript%3Ealert('XSS')%3C/script%3E")
+                .unwrap()
+                .reasons,
+            ["xss:script_tag"]
+        );
+        assert!(detector.detect("alert('XSS')").is_none());
+        assert!(detector.detect("R&D: 50% of <b>").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_injection_detectors() {
+        let detection: Detection = PatternDetector::path_traversal()
+            .detect("../..%2f..%2fetc/passwd%00")
+            .unwrap();
+        assert_eq!(detection.source, DetectionSource::PathTraversal);
+        assert_eq!(
+            detection.reasons,
+            [
+                "path_traversal:dot_segment",
+                "path_traversal:encoded_dot_segment",
+                "path_traversal:null_byte"
+            ]
+        );
+        assert_eq!(detection.severity, Severity::High);
+
+        assert_eq!(
+            PatternDetector::command()
+                .detect("report; rm -rf /")
+                .unwrap()
+                .reasons,
+            ["command:command_chaining"]
+        );
+        assert!(PatternDetector::ldap().detect("*)(uid=*").is_some());
+        assert!(PatternDetector::xpath().detect("']|//*|//*['").is_some());
+        assert!(PatternDetector::command().detect("Tom && Jerry").is_none());
+    }
+}
@@ -0,0 +1,45 @@
+use crate::structs::column_stats::ColumnStats;
+use crate::structs::csv_file::CsvFile;
+use serde::{Deserialize, Serialize};
+
+/// Represents the summary of the analysis of a file, written at the end of a report.
+#[derive(Serialize, Deserialize, Clone)]
+#[non_exhaustive]
+pub struct ReportSummary {
+    pub analysed_file: String,
+    pub encoding: String,
+    pub anomalies: usize,
+    #[serde(default)]
+    pub structure_findings: usize,
+    pub ai_analyze: u32,
+    pub regex_analyze: u32,
+    pub time_ms: u128,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnStats>,
+}
+
+impl ReportSummary {
+    /// Create the summary of the analysis of a CSV file.
+    #[inline]
+    #[must_use]
+    pub fn new(
+        csv_file: &CsvFile,
+        anomalies: usize,
+        structure_findings: usize,
+        ai_analyze: u32,
+        regex_analyze: u32,
+        time_ms: u128,
+        columns: Vec<ColumnStats>,
+    ) -> Self {
+        Self {
+            analysed_file: csv_file.csv_file_path.clone(),
+            encoding: String::from(csv_file.encoding.name()),
+            anomalies,
+            structure_findings,
+            ai_analyze,
+            regex_analyze,
+            time_ms,
+            columns,
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use crate::enums::severity::Severity;
+use serde::{Deserialize, Serialize};
+
+/// Represents a named regex pattern marking values as safe or unsafe.
+/// A rule without columns applies to every column, otherwise only to the columns with one of the given headers.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct Rule {
+    pub name: String,
+    pub pattern: String,
+    #[serde(default)]
+    pub severity: Severity,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<String>,
+}
+
+impl Rule {
+    /// Create a new rule applying to every column.
+    #[inline]
+    #[must_use]
+    pub fn new(name: &str, pattern: &str, severity: Severity) -> Self {
+        Self {
+            name: String::from(name),
+            pattern: String::from(pattern),
+            severity,
+            columns: Vec::new(),
+        }
+    }
+
+    /// Return true if the rule applies to the column with the given header.
+    #[inline]
+    #[must_use]
+    pub fn applies_to(&self, column_name: &str) -> bool {
+        self.columns.is_empty() || self.columns.iter().any(|column| column == column_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_rule_applies_to() {
+        let mut rule: Rule = Rule::new("sku", r"^[A-Z]{2}-\d{2}/\d{2}$", Severity::Low);
+        assert!(rule.applies_to("Sku"));
+        assert!(rule.applies_to("Comment"));
+
+        rule.columns = vec![String::from("Sku")];
+        assert!(rule.applies_to("Sku"));
+        assert!(!rule.applies_to("Comment"));
+    }
+}
@@ -0,0 +1,17 @@
+/// Represents the indexes of the allow and deny rules of a `RuleSet` whose pattern matches a value,
+/// before the columns of the rules are taken into account.
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub struct RuleMatches {
+    pub allow: Vec<usize>,
+    pub deny: Vec<usize>,
+}
+
+impl RuleMatches {
+    /// Return true if the value matches the pattern of at least one deny rule.
+    #[inline]
+    #[must_use]
+    pub fn is_denied(&self) -> bool {
+        !self.deny.is_empty()
+    }
+}
@@ -0,0 +1,272 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::rule_mode::RuleMode;
+use crate::enums::severity::Severity;
+use crate::structs::detection::Detection;
+use crate::structs::pattern_detector::PatternDetector;
+use crate::structs::rule::Rule;
+use crate::structs::rule_matches::RuleMatches;
+use crate::structs::rules_file::RulesFile;
+use crate::structs::sql_injection_detector::SqlInjectionDetector;
+use crate::structs::unicode_detector::UnicodeDetector;
+use crate::traits::detector::Detector;
+use crate::utils::regex::{safe_value_patterns, unsafe_value_patterns};
+use regex::{Regex, RegexSet};
+use std::path::Path;
+
+/// Represents the allow and deny rules used to filter the values before the model.
+/// A value is sent to the model when it matches a deny rule of its column and no allow rule of its column.
+/// The detectors run before the rules: the values they report are anomalies without going through the model,
+/// unless an allow rule restricted to their column matches them.
+#[non_exhaustive]
+pub struct RuleSet {
+    allow_rules: Vec<Rule>,
+    allow_regex_set: RegexSet,
+    deny_rules: Vec<Rule>,
+    deny_regex_set: RegexSet,
+    detectors: Vec<Box<dyn Detector>>,
+}
+
+impl Default for RuleSet {
+    fn default() -> Self {
+        Self::new(Self::builtin_allow_rules(), Self::builtin_deny_rules())
+            .expect("The built-in rules are valid")
+    }
+}
+
+impl RuleSet {
+    /// Create a rule set from allow and deny rules, checking that each pattern is a valid regex.
+    pub fn new(allow_rules: Vec<Rule>, deny_rules: Vec<Rule>) -> Result<Self, DataLintError> {
+        Ok(Self {
+            allow_regex_set: Self::regex_set(&allow_rules)?,
+            allow_rules,
+            deny_regex_set: Self::regex_set(&deny_rules)?,
+            deny_rules,
+            detectors: Self::builtin_detectors(),
+        })
+    }
+
+    /// Create a rule set from a rules file, merged with the built-in rules or replacing them.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DataLintError> {
+        let rules_file: RulesFile = RulesFile::from_file(path)?;
+
+        let (mut allow_rules, mut deny_rules): (Vec<Rule>, Vec<Rule>) = match rules_file.mode {
+            RuleMode::Merge => (Self::builtin_allow_rules(), Self::builtin_deny_rules()),
+            RuleMode::Replace => (Vec::new(), Vec::new()),
+        };
+        allow_rules.extend(rules_file.allow);
+        deny_rules.extend(rules_file.deny);
+
+        Self::new(allow_rules, deny_rules)
+    }
+
+    /// Return the built-in rules marking numbers, dates, emails, plain words in any script and phone numbers as safe.
+    #[must_use]
+    pub fn builtin_allow_rules() -> Vec<Rule> {
+        safe_value_patterns()
+            .iter()
+            .map(|(name, pattern)| Rule::new(name, pattern, Severity::Low))
+            .collect()
+    }
+
+    /// Return the built-in rules sending SQL keywords and special characters to the model,
+    /// the SQL injections themselves being reported by their detector.
+    #[must_use]
+    pub fn builtin_deny_rules() -> Vec<Rule> {
+        unsafe_value_patterns()
+            .iter()
+            .map(|&(name, pattern, severity)| Rule::new(name, pattern, severity))
+            .collect()
+    }
+
+    /// Return the built-in detectors, run on every value whatever the rules.
+    #[must_use]
+    pub fn builtin_detectors() -> Vec<Box<dyn Detector>> {
+        vec![
+            Box::new(PatternDetector::formula()),
+            Box::new(PatternDetector::xss()),
+            Box::new(SqlInjectionDetector),
+            Box::new(PatternDetector::command()),
+            Box::new(PatternDetector::path_traversal()),
+            Box::new(PatternDetector::ldap()),
+            Box::new(PatternDetector::xpath()),
+            Box::new(UnicodeDetector),
+        ]
+    }
+
+    /// Return the allow rules of the rule set.
+    #[inline]
+    #[must_use]
+    pub fn allow_rules(&self) -> &[Rule] {
+        &self.allow_rules
+    }
+
+    /// Return the deny rules of the rule set.
+    #[inline]
+    #[must_use]
+    pub fn deny_rules(&self) -> &[Rule] {
+        &self.deny_rules
+    }
+
+    /// Return the rules whose pattern matches the value, whatever their columns.
+    #[must_use]
+    pub fn matches(&self, value: &str) -> RuleMatches {
+        RuleMatches {
+            allow: self.allow_regex_set.matches(value).into_iter().collect(),
+            deny: self.deny_regex_set.matches(value).into_iter().collect(),
+        }
+    }
+
+    /// Return the most severe detection of the value by the detectors, the first detector winning a tie.
+    #[must_use]
+    pub fn detect(&self, value: &str) -> Option<Detection> {
+        self.detectors
+            .iter()
+            .filter_map(|detector| detector.detect(value))
+            .reduce(|most_severe, detection| {
+                if detection.severity > most_severe.severity {
+                    detection
+                } else {
+                    most_severe
+                }
+            })
+    }
+
+    /// Return the detection of a value of the column, unless an allow rule restricted to the column matches the value:
+    /// such a rule marks the known values of its column as safe, while the allow rules of every column do not apply
+    /// to the detectors.
+    #[must_use]
+    pub fn detect_in_column(&self, value: &str, column_name: &str) -> Option<Detection> {
+        let detection: Detection = self.detect(value)?;
+
+        let allowed: bool = self
+            .allow_regex_set
+            .matches(value.trim())
+            .into_iter()
+            .filter_map(|index| self.allow_rules.get(index))
+            .any(|rule| !rule.columns.is_empty() && rule.applies_to(column_name));
+
+        (!allowed).then_some(detection)
+    }
+
+    /// Return the allow rules of the column among the matching rules.
+    #[must_use]
+    pub fn allowed_by<'a>(
+        &'a self,
+        rule_matches: &RuleMatches,
+        column_name: &str,
+    ) -> Vec<&'a Rule> {
+        Self::rules_of_column(&self.allow_rules, &rule_matches.allow, column_name)
+    }
+
+    /// Return the deny rules of the column among the matching rules,
+    /// or nothing when an allow rule of the column also matches.
+    #[must_use]
+    pub fn denied_by<'a>(&'a self, rule_matches: &RuleMatches, column_name: &str) -> Vec<&'a Rule> {
+        if !self.allowed_by(rule_matches, column_name).is_empty() {
+            return Vec::new();
+        }
+
+        Self::rules_of_column(&self.deny_rules, &rule_matches.deny, column_name)
+    }
+
+    /// Return the rules at the given indexes applying to the column.
+    fn rules_of_column<'a>(
+        rules: &'a [Rule],
+        indexes: &[usize],
+        column_name: &str,
+    ) -> Vec<&'a Rule> {
+        indexes
+            .iter()
+            .filter_map(|&index| rules.get(index))
+            .filter(|rule| rule.applies_to(column_name))
+            .collect()
+    }
+
+    /// Compile the patterns of the rules into a `RegexSet`, naming the first invalid rule.
+    fn regex_set(rules: &[Rule]) -> Result<RegexSet, DataLintError> {
+        for rule in rules {
+            Regex::new(&rule.pattern).map_err(|e| {
+                DataLintError::Config(format!("Invalid pattern of the rule {}: {e}", rule.name))
+            })?;
+        }
+
+        RegexSet::new(rules.iter().map(|rule| &rule.pattern))
+            .map_err(|e| DataLintError::Config(format!("Error compiling the rules: {e}")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_builtin_rule_set() {
+        let rule_set: RuleSet = RuleSet::default();
+
+        let rule_matches: RuleMatches = rule_set.matches("DROP TABLE x;");
+        let names: Vec<&str> = rule_set
+            .denied_by(&rule_matches, "Comment")
+            .iter()
+            .map(|rule| rule.name.as_str())
+            .collect();
+        assert_eq!(names, ["sql_keyword_regex", "illegal_char_regex"]);
+
+        assert!(
+            rule_set
+                .denied_by(&rule_set.matches("hello"), "Comment")
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_builtin_allow_rules_match_whole_values() {
+        let rule_set: RuleSet = RuleSet::default();
+
+        let rule_matches: RuleMatches = rule_set.matches("1; DROP TABLE users");
+        assert!(rule_set.allowed_by(&rule_matches, "Comment").is_empty());
+        assert!(!rule_set.denied_by(&rule_matches, "Comment").is_empty());
+
+        let rule_matches: RuleMatches = rule_set.matches("+33 6 12 34 56 78");
+        assert!(!rule_set.allowed_by(&rule_matches, "Comment").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_column_scoped_allow_rule() {
+        let mut sku: Rule = Rule::new("sku", r"^[A-Z]{2}-\d{2}/\d{2}$", Severity::Low);
+        sku.columns = vec![String::from("Sku")];
+
+        let rule_set: RuleSet = RuleSet::new(vec![sku], RuleSet::builtin_deny_rules()).unwrap();
+
+        let rule_matches: RuleMatches = rule_set.matches("AB-12/34");
+        assert!(rule_matches.is_denied());
+        assert!(rule_set.denied_by(&rule_matches, "Sku").is_empty());
+        assert!(!rule_set.denied_by(&rule_matches, "Comment").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_column_scoped_allow_rule_on_detection() {
+        let mut formula: Rule = Rule::new("formula", r"^=SUM\(", Severity::Low);
+        formula.columns = vec![String::from("Total")];
+        let mut allow_rules: Vec<Rule> = RuleSet::builtin_allow_rules();
+        allow_rules.push(formula);
+        allow_rules.push(Rule::new("any_sum", r"^=SUM\(", Severity::Low));
+
+        let rule_set: RuleSet = RuleSet::new(allow_rules, RuleSet::builtin_deny_rules()).unwrap();
+
+        assert!(rule_set.detect_in_column("=SUM(A1:A3)", "Total").is_none());
+        // The allow rules of every column do not apply to the detectors
+        assert!(
+            rule_set
+                .detect_in_column("=SUM(A1:A3)", "Comment")
+                .is_some()
+        );
+        assert!(rule_set.detect_in_column("<script>", "Total").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_rule() {
+        let invalid: Rule = Rule::new("broken", "(", Severity::High);
+        let error: DataLintError = RuleSet::new(Vec::new(), vec![invalid]).err().unwrap();
+        assert!(error.to_string().contains("broken"));
+    }
+}
@@ -0,0 +1,40 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::rule_mode::RuleMode;
+use crate::structs::rule::Rule;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Represents a file of user-defined rules, in TOML when its extension is `.toml` and in JSON otherwise.
+/// `allow` rules mark the values they match as safe, `deny` rules send the values they match to the model.
+#[derive(Default, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct RulesFile {
+    #[serde(default)]
+    pub mode: RuleMode,
+    #[serde(default)]
+    pub allow: Vec<Rule>,
+    #[serde(default)]
+    pub deny: Vec<Rule>,
+}
+
+impl RulesFile {
+    /// Read a rules file, parsed as TOML or JSON depending on its extension.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, DataLintError> {
+        let path: &Path = path.as_ref();
+        let content: String = std::fs::read_to_string(path)?;
+
+        let is_toml: bool = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+
+        let rules_file: Result<Self, String> = if is_toml {
+            toml::from_str(&content).map_err(|e| e.to_string())
+        } else {
+            serde_json::from_str(&content).map_err(|e| e.to_string())
+        };
+
+        rules_file.map_err(|e| {
+            DataLintError::Config(format!("Error reading rules from {}: {e}", path.display()))
+        })
+    }
+}
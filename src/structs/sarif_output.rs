@@ -0,0 +1,463 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::detection_source::DetectionSource;
+use crate::enums::severity::Severity;
+use crate::enums::structure_issue::StructureIssue;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::column_stats::ColumnStats;
+use crate::structs::field_region::FieldRegion;
+use crate::structs::json_output::JsonOutput;
+use crate::structs::structure_finding::StructureFinding;
+use serde::Serialize;
+use std::io::{BufWriter, Write};
+
+/// Represents a report in the SARIF 2.1.0 format, read by code scanning dashboards and viewers.
+/// Each anomaly is a result located at the lines and columns of its field in the CSV file, when they are known,
+/// its column being also given as a logical location.
+/// Each structural finding is a result of the rule of its issue, located at its line.
+#[derive(Serialize)]
+#[non_exhaustive]
+pub struct SarifOutput {
+    #[serde(rename = "$schema")]
+    pub schema: &'static str,
+    pub version: &'static str,
+    pub runs: Vec<SarifRun>,
+}
+
+/// Represents a run of DataLint on one CSV file.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifRun {
+    pub tool: SarifTool,
+    pub column_kind: &'static str, // Unit of the columns of the regions
+    pub artifacts: Vec<SarifArtifact>,
+    pub results: Vec<SarifResult>,
+    pub properties: SarifRunProperties,
+}
+
+/// Represents the tool that produced the run.
+#[derive(Serialize)]
+#[non_exhaustive]
+pub struct SarifTool {
+    pub driver: SarifDriver,
+}
+
+/// Represents DataLint and the rules it reports.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifDriver {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub information_uri: &'static str,
+    pub rules: Vec<SarifRule>,
+}
+
+/// Represents a rule, one per detection source and one per structural issue.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifRule {
+    pub id: &'static str,
+    pub short_description: SarifMessage,
+    pub default_configuration: SarifConfiguration,
+}
+
+/// Represents the default level of a rule.
+#[derive(Serialize)]
+#[non_exhaustive]
+pub struct SarifConfiguration {
+    pub level: &'static str,
+}
+
+/// Represents a plain text message.
+#[derive(Serialize)]
+#[non_exhaustive]
+pub struct SarifMessage {
+    pub text: String,
+}
+
+/// Represents the analysed CSV file and its encoding.
+#[derive(Serialize)]
+#[non_exhaustive]
+pub struct SarifArtifact {
+    pub location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub encoding: String,
+}
+
+/// Represents the location of the analysed CSV file.
+#[derive(Serialize)]
+#[non_exhaustive]
+pub struct SarifArtifactLocation {
+    pub uri: String,
+}
+
+/// Represents an anomaly, with its score and detection source in its properties and its severity as level,
+/// or a structural finding, with its issue in its properties.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifResult {
+    pub rule_id: &'static str,
+    pub level: &'static str,
+    pub message: SarifMessage,
+    pub locations: Vec<SarifLocation>,
+    pub properties: SarifProperties,
+}
+
+/// Represents the properties of a result, depending on whether it is an anomaly or a structural finding.
+#[derive(Serialize)]
+#[serde(untagged)]
+pub enum SarifProperties {
+    Anomaly(SarifResultProperties),
+    Structure(SarifStructureProperties),
+}
+
+/// Represents the line of the CSV file and the column where the anomaly was found.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifLocation {
+    pub physical_location: SarifPhysicalLocation,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub logical_locations: Vec<SarifLogicalLocation>,
+}
+
+/// Represents the file and the region of the anomaly.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifPhysicalLocation {
+    pub artifact_location: SarifArtifactLocation,
+    pub region: SarifRegion,
+}
+
+/// Represents the lines and columns of the field of an anomaly and the value found in it,
+/// or the line of a structural finding. The end column is the one following the field.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifRegion {
+    pub start_line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snippet: Option<SarifMessage>,
+}
+
+impl SarifRegion {
+    /// Create a region starting at the given line, spanning the field region if it is known.
+    #[must_use]
+    pub fn new(
+        line: u32,
+        field_region: Option<FieldRegion>,
+        snippet: Option<SarifMessage>,
+    ) -> Self {
+        Self {
+            start_line: field_region.map_or(line, |region| region.start_line),
+            start_column: field_region.map(|region| region.start_column),
+            end_line: field_region.map(|region| region.end_line),
+            end_column: field_region.map(|region| region.end_column),
+            snippet,
+        }
+    }
+}
+
+/// Represents the column of the anomaly by its header.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifLogicalLocation {
+    pub name: String,
+    pub fully_qualified_name: String,
+    pub index: usize,
+}
+
+/// Represents the score of an anomaly and the detector that reported it.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifResultProperties {
+    pub score: f32,
+    pub threshold: f32,
+    pub source: DetectionSource,
+    pub column: String,
+    pub column_index: usize,
+    pub reasons: Vec<String>,
+    pub severity: Severity,
+}
+
+/// Represents the issue of a structural finding and the column it concerns, if any.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifStructureProperties {
+    pub issue: StructureIssue,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    pub severity: Severity,
+}
+
+/// Represents the counters of the analysis and the statistics of each column.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct SarifRunProperties {
+    pub ai_analyze: u32,
+    pub regex_analyze: u32,
+    pub time_ms: u128,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub columns: Vec<ColumnStats>,
+}
+
+impl SarifOutput {
+    /// URI of the JSON schema of SARIF 2.1.0.
+    pub const SCHEMA: &'static str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+    /// Version of the SARIF format.
+    pub const VERSION: &'static str = "2.1.0";
+
+    /// Default level of the rules, the level of each result depending on the severity of its anomaly.
+    const LEVEL: &'static str = "warning";
+
+    /// Unit of the columns, the regions counting the characters of the decoded file.
+    const COLUMN_KIND: &'static str = "unicodeCodePoints";
+
+    /// Create a `SarifOutput` from the report of an analysis.
+    #[must_use]
+    pub fn new(report: &JsonOutput) -> Self {
+        let uri: String = Self::artifact_uri(&report.analysed_file);
+
+        let source_rules = DetectionSource::ALL.iter().map(|source| SarifRule {
+            id: source.as_str(),
+            short_description: SarifMessage {
+                text: String::from(source.description()),
+            },
+            default_configuration: SarifConfiguration { level: Self::LEVEL },
+        });
+        let structure_rules = StructureIssue::ALL.iter().map(|issue| SarifRule {
+            id: issue.rule_id(),
+            short_description: SarifMessage {
+                text: String::from(issue.description()),
+            },
+            default_configuration: SarifConfiguration {
+                level: issue.severity().sarif_level(),
+            },
+        });
+        let rules: Vec<SarifRule> = source_rules.chain(structure_rules).collect();
+
+        let results: Vec<SarifResult> = report
+            .anomalies
+            .iter()
+            .map(|anomaly| Self::result(anomaly, &uri))
+            .chain(
+                report
+                    .structure
+                    .iter()
+                    .map(|finding| Self::structure_result(finding, &uri)),
+            )
+            .collect();
+
+        Self {
+            schema: Self::SCHEMA,
+            version: Self::VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "DataLint",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_REPOSITORY"),
+                        rules,
+                    },
+                },
+                column_kind: Self::COLUMN_KIND,
+                artifacts: vec![SarifArtifact {
+                    location: SarifArtifactLocation { uri },
+                    encoding: report.encoding.clone(),
+                }],
+                results,
+                properties: SarifRunProperties {
+                    ai_analyze: report.ai_analyze,
+                    regex_analyze: report.regex_analyze,
+                    time_ms: report.time_ms,
+                    columns: report.columns.clone(),
+                },
+            }],
+        }
+    }
+
+    /// Map an anomaly to a SARIF result located in the given file.
+    fn result(anomaly: &Anomaly, uri: &str) -> SarifResult {
+        SarifResult {
+            rule_id: anomaly.source.as_str(),
+            level: anomaly.severity.sarif_level(),
+            message: SarifMessage {
+                text: Self::anomaly_message(anomaly),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: String::from(uri),
+                    },
+                    region: SarifRegion::new(
+                        anomaly.line,
+                        anomaly.region,
+                        Some(SarifMessage {
+                            text: anomaly.value.clone(),
+                        }),
+                    ),
+                },
+                logical_locations: vec![SarifLogicalLocation {
+                    name: anomaly.column.clone(),
+                    fully_qualified_name: format!("{uri}#{}", anomaly.column),
+                    index: anomaly.column_index,
+                }],
+            }],
+            properties: SarifProperties::Anomaly(SarifResultProperties {
+                score: anomaly.score,
+                threshold: anomaly.threshold,
+                source: anomaly.source,
+                column: anomaly.column.clone(),
+                column_index: anomaly.column_index,
+                reasons: anomaly.reasons.clone(),
+                severity: anomaly.severity,
+            }),
+        }
+    }
+
+    /// Return the message of an anomaly: the model compares its score to the threshold of the column,
+    /// while the detectors and the schema report it from their own score.
+    fn anomaly_message(anomaly: &Anomaly) -> String {
+        if anomaly.source == DetectionSource::Model {
+            format!(
+                "Unsafe value {:?} in column {:?} (score {} above the threshold {}): {}",
+                anomaly.value,
+                anomaly.column,
+                anomaly.score,
+                anomaly.threshold,
+                anomaly.reason()
+            )
+        } else {
+            format!(
+                "Unsafe value {:?} in column {:?} reported by the {} detector (score {}, reported from {}): {}",
+                anomaly.value,
+                anomaly.column,
+                anomaly.source.as_str(),
+                anomaly.score,
+                anomaly.threshold,
+                anomaly.reason()
+            )
+        }
+    }
+
+    /// Map a structural finding to a SARIF result of the rule of its issue, located at its line in the given file.
+    fn structure_result(finding: &StructureFinding, uri: &str) -> SarifResult {
+        let column: String = finding
+            .column
+            .as_ref()
+            .map(|column| format!(" in column {column:?}"))
+            .unwrap_or_default();
+
+        SarifResult {
+            rule_id: finding.issue.rule_id(),
+            level: finding.severity.sarif_level(),
+            message: SarifMessage {
+                text: format!("Structural problem{column}: {}", finding.message),
+            },
+            locations: vec![SarifLocation {
+                physical_location: SarifPhysicalLocation {
+                    artifact_location: SarifArtifactLocation {
+                        uri: String::from(uri),
+                    },
+                    region: SarifRegion::new(finding.line, None, None),
+                },
+                logical_locations: Vec::new(),
+            }],
+            properties: SarifProperties::Structure(SarifStructureProperties {
+                issue: finding.issue,
+                column: finding.column.clone(),
+                severity: finding.severity,
+            }),
+        }
+    }
+
+    /// Return the URI of a file path: relative paths stay relative, absolute paths use the `file` scheme.
+    #[must_use]
+    fn artifact_uri(file_path: &str) -> String {
+        let path: String = file_path
+            .replace('\\', "/")
+            .replace('%', "%25")
+            .replace(' ', "%20")
+            .replace('#', "%23");
+
+        if path.starts_with('/') {
+            format!("file://{path}")
+        } else if path.as_bytes().get(1) == Some(&b':') {
+            format!("file:///{path}")
+        } else {
+            path
+        }
+    }
+
+    /// Write the `SarifOutput` in pretty JSON format to any writer, such as the standard output
+    pub fn write_to<W: Write>(&self, writer: W) -> Result<(), DataLintError> {
+        let mut writer: BufWriter<W> = BufWriter::new(writer);
+        serde_json::to_writer_pretty(&mut writer, self).map_err(std::io::Error::from)?;
+        writeln!(writer)?;
+        Ok(writer.flush()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_artifact_uri() {
+        assert_eq!(SarifOutput::artifact_uri("data/file.csv"), "data/file.csv");
+        assert_eq!(
+            SarifOutput::artifact_uri("/tmp/my file.csv"),
+            "file:///tmp/my%20file.csv"
+        );
+        assert_eq!(
+            SarifOutput::artifact_uri("C:\\data\\file.csv"),
+            "file:///C:/data/file.csv"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_anomaly_message() {
+        let mut anomaly: Anomaly =
+            Anomaly::new(String::from("admin'--"), String::from("Name"), 2, 0.7, 0.5);
+        anomaly.source = DetectionSource::Sql;
+        assert_eq!(
+            SarifOutput::anomaly_message(&anomaly),
+            "Unsafe value \"admin'--\" in column \"Name\" reported by the sql detector (score 0.7, reported from 0.5): sql"
+        );
+
+        anomaly.source = DetectionSource::Model;
+        assert!(
+            SarifOutput::anomaly_message(&anomaly).contains("score 0.7 above the threshold 0.5")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_region() {
+        let region: SarifRegion = SarifRegion::new(4, None, None);
+        assert_eq!(
+            serde_json::to_value(&region).unwrap(),
+            serde_json::json!({"startLine": 4})
+        );
+
+        let region: SarifRegion = SarifRegion::new(4, Some(FieldRegion::new((4, 6), (5, 3))), None);
+        assert_eq!(
+            serde_json::to_value(&region).unwrap(),
+            serde_json::json!({"startLine": 4, "startColumn": 6, "endLine": 5, "endColumn": 3})
+        );
+    }
+}
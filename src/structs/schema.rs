@@ -0,0 +1,64 @@
+use crate::enums::column_type::ColumnType;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Represents the types expected for the columns of the CSV files, keyed by column header or by column index.
+/// A header takes precedence over an index, and columns without a type are analysed as free text.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+#[non_exhaustive]
+pub struct Schema {
+    pub columns: HashMap<String, ColumnType>,
+}
+
+impl Schema {
+    /// Return true if the schema does not declare any column type.
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// Return the type of the column with the given index and header, if the schema declares one.
+    #[must_use]
+    pub fn column_type(&self, column_index: usize, column_name: &str) -> Option<&ColumnType> {
+        self.columns
+            .get(column_name)
+            .or_else(|| self.columns.get(&column_index.to_string()))
+    }
+
+    /// Return the type of each column of the headers, in the order of the headers.
+    #[must_use]
+    pub fn column_types<'a, I>(&self, headers: I) -> Vec<Option<&ColumnType>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        headers
+            .into_iter()
+            .enumerate()
+            .map(|(column_index, column_name)| self.column_type(column_index, column_name))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_schema_column_type() {
+        let schema: Schema = serde_json::from_str(
+            r#"{"Phone": {"type": "phone"}, "0": {"type": "integer"}, "1": {"type": "skip"}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(schema.column_type(3, "Phone"), Some(&ColumnType::Phone));
+        assert_eq!(schema.column_type(0, "Id"), Some(&ColumnType::Integer));
+        assert_eq!(schema.column_type(1, "Phone"), Some(&ColumnType::Phone));
+        assert_eq!(schema.column_type(2, "Comment"), None);
+        assert_eq!(
+            schema.column_types(["Id", "Name"]),
+            vec![Some(&ColumnType::Integer), Some(&ColumnType::Skip)]
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use crate::enums::drift_kind::DriftKind;
+use serde::{Deserialize, Serialize};
+
+/// Represents a difference between a CSV file and the profile it is compared to, on one of its columns.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct SchemaDrift {
+    pub kind: DriftKind,
+    pub column: String,
+    pub message: String,
+}
+
+impl SchemaDrift {
+    /// Create a new instance of `SchemaDrift`.
+    #[inline]
+    #[must_use]
+    pub fn new(kind: DriftKind, column: &str, message: String) -> Self {
+        Self {
+            kind,
+            column: String::from(column),
+            message,
+        }
+    }
+
+    /// Return a formatted string representation of the drift.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> String {
+        format!("[{}] {}: {}", self.kind.as_str(), self.column, self.message)
+    }
+}
@@ -0,0 +1,17 @@
+/// Represents the score given by the model to a value, kept to report every occurrence of the value
+/// without inferring it again, with the tokens that raise the score the most when it can be an anomaly.
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub struct ScoredValue {
+    pub score: f64,
+    pub top_tokens: Vec<String>,
+}
+
+impl ScoredValue {
+    /// Creates a new instance of `ScoredValue`.
+    #[inline]
+    #[must_use]
+    pub const fn new(score: f64, top_tokens: Vec<String>) -> Self {
+        Self { score, top_tokens }
+    }
+}
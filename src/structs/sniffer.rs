@@ -0,0 +1,209 @@
+use crate::enums::separator::SeparatorType;
+
+/// Represents the delimiter chosen by the `Sniffer`, with a confidence between 0 and 1.
+#[derive(Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub struct SniffResult {
+    pub delimiter: u8,
+    pub confidence: f32,
+}
+
+/// Detects the delimiter of CSV data by counting the fields of the first records for each candidate,
+/// ignoring the delimiters found between quotes.
+#[non_exhaustive]
+pub struct Sniffer;
+
+impl Sniffer {
+    /// Candidates in order of priority, used to break ties between equally consistent delimiters.
+    const CANDIDATES: [SeparatorType; 5] = [
+        SeparatorType::Semicolon,
+        SeparatorType::Tab,
+        SeparatorType::Pipe,
+        SeparatorType::Null,
+        SeparatorType::Comma,
+    ];
+
+    /// Maximum number of records inspected in the sample.
+    const MAX_RECORDS: usize = 50;
+
+    /// Quote character around fields that may contain the delimiter.
+    const QUOTE: u8 = b'"';
+
+    /// Return the most consistent delimiter of the sample, or `None` if no candidate splits its records.
+    /// The score of a candidate is the share of records having its most frequent field count,
+    /// the confidence is lowered when another candidate is almost as consistent.
+    #[must_use]
+    pub fn sniff(sample: &[u8]) -> Option<SniffResult> {
+        let mut best: Option<(u8, f32, usize)> = None; // (delimiter, consistency, field count)
+        let mut runner_up_consistency: f32 = 0.0;
+
+        for candidate in Self::CANDIDATES {
+            let delimiter: u8 = u8::from(candidate);
+            let Some((consistency, field_count)) = Self::score(sample, delimiter) else {
+                continue;
+            };
+
+            match best {
+                Some((_, best_consistency, best_field_count))
+                    if consistency < best_consistency
+                        || (consistency == best_consistency && field_count <= best_field_count) =>
+                {
+                    runner_up_consistency = runner_up_consistency.max(consistency);
+                }
+                _ => {
+                    if let Some((_, best_consistency, _)) = best {
+                        runner_up_consistency = runner_up_consistency.max(best_consistency);
+                    }
+                    best = Some((delimiter, consistency, field_count));
+                }
+            }
+        }
+
+        best.map(|(delimiter, consistency, _)| SniffResult {
+            delimiter,
+            confidence: consistency * consistency / (consistency + runner_up_consistency),
+        })
+    }
+
+    /// Return the consistency and the most frequent field count of the records split on the delimiter,
+    /// or `None` if the delimiter never splits a record.
+    fn score(sample: &[u8], delimiter: u8) -> Option<(f32, usize)> {
+        let field_counts: Vec<usize> = Self::count_fields(sample, delimiter);
+
+        let mut occurrences: Vec<(usize, usize)> = Vec::new(); // (field count, number of records)
+        for &count in &field_counts {
+            match occurrences
+                .iter_mut()
+                .find(|(field_count, _)| *field_count == count)
+            {
+                Some((_, records)) => *records += 1,
+                None => occurrences.push((count, 1)),
+            }
+        }
+
+        let (field_count, records): (usize, usize) = occurrences
+            .into_iter()
+            .max_by_key(|&(field_count, records)| (records, field_count))?;
+
+        if field_count < 2 {
+            return None;
+        }
+
+        Some((records as f32 / field_counts.len() as f32, field_count))
+    }
+
+    /// Count the fields of each complete record of the sample, skipping blank lines.
+    /// A record still open at the end of the sample is ignored as it may have been truncated.
+    fn count_fields(sample: &[u8], delimiter: u8) -> Vec<usize> {
+        let mut field_counts: Vec<usize> = Vec::new();
+        let mut fields: usize = 1;
+        let mut in_quotes: bool = false;
+        let mut has_content: bool = false;
+        let mut bytes = sample.iter().peekable();
+
+        while let Some(&byte) = bytes.next() {
+            if field_counts.len() >= Self::MAX_RECORDS {
+                return field_counts;
+            }
+
+            if in_quotes {
+                if byte == Self::QUOTE && bytes.next_if_eq(&&Self::QUOTE).is_none() {
+                    in_quotes = false;
+                }
+                continue;
+            }
+
+            match byte {
+                Self::QUOTE => in_quotes = true,
+                b'\n' => {
+                    if has_content {
+                        field_counts.push(fields);
+                    }
+                    fields = 1;
+                    has_content = false;
+                    continue;
+                }
+                b'\r' => continue,
+                _ if byte == delimiter => fields += 1,
+                _ => {}
+            }
+            has_content = true;
+        }
+
+        // The last record is complete if the sample holds a single unterminated line
+        if has_content && !in_quotes && field_counts.is_empty() {
+            field_counts.push(fields);
+        }
+
+        field_counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_sniff_simple_delimiters() {
+        for delimiter in [b',', b';', b'\t', b'|'] {
+            let sep: char = delimiter as char;
+            let sample: String =
+                format!("Name{sep}Age{sep}City\nJohn{sep}25{sep}New York\nJane{sep}30{sep}Paris\n");
+
+            let result: SniffResult = Sniffer::sniff(sample.as_bytes()).unwrap();
+            assert_eq!(result.delimiter, delimiter, "Error on: {sep:?}");
+            assert!((result.confidence - 1.0).abs() < f32::EPSILON);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sniff_ignores_quoted_delimiters() {
+        const SAMPLE: &[u8] =
+            b"\"Last, First\";Age;City\n\"Doe, John\";25;\"Paris; France\"\n\"Doe, Jane\";30;Lyon\n";
+
+        let result: SniffResult = Sniffer::sniff(SAMPLE).unwrap();
+        assert_eq!(result.delimiter, b';');
+        assert!((result.confidence - 1.0).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_sniff_prefers_consistent_delimiter() {
+        // The header contains several candidates, only the comma splits every record the same way
+        const SAMPLE: &[u8] = b"id,name;alias,city\n1,John;J,Paris\n2,Jane,Lyon\n3,Bob,Nice\n";
+
+        let result: SniffResult = Sniffer::sniff(SAMPLE).unwrap();
+        assert_eq!(result.delimiter, b',');
+        assert!(result.confidence > 0.5);
+
+        // The result does not change between runs
+        for _ in 0..10 {
+            assert_eq!(Sniffer::sniff(SAMPLE), Some(result.clone()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sniff_ambiguous_sample() {
+        const SAMPLE: &[u8] = b"a;b,c\n1;2,3\n";
+
+        let result: SniffResult = Sniffer::sniff(SAMPLE).unwrap();
+        assert_eq!(result.delimiter, b';');
+        assert!((result.confidence - 0.5).abs() < f32::EPSILON);
+    }
+
+    #[tokio::test]
+    async fn test_sniff_without_delimiter() {
+        assert_eq!(Sniffer::sniff(b""), None);
+        assert_eq!(Sniffer::sniff(b"\n\n"), None);
+        assert_eq!(Sniffer::sniff(b"Name\nJohn\nJane\n"), None);
+    }
+
+    #[tokio::test]
+    async fn test_sniff_truncated_sample() {
+        // The last record is cut in the middle of a quoted field
+        const SAMPLE: &[u8] = b"Name,Comment\nJohn,ok\nJane,\"multi\nline, with comma";
+
+        let result: SniffResult = Sniffer::sniff(SAMPLE).unwrap();
+        assert_eq!(result.delimiter, b',');
+        assert!((result.confidence - 1.0).abs() < f32::EPSILON);
+    }
+}
@@ -0,0 +1,406 @@
+use crate::enums::detection_source::DetectionSource;
+use crate::enums::severity::Severity;
+use crate::enums::sql_signal::SqlSignal;
+use crate::enums::sql_token_kind::SqlTokenKind;
+use crate::structs::detection::Detection;
+use crate::structs::sql_token::SqlToken;
+use crate::traits::detector::Detector;
+
+/// Represents the detector of the SQL injections, scoring the structure of a value read as SQL rather than
+/// its keywords: quotes closing a string literal, comments, conditions always true, stacked queries and `UNION SELECT`.
+/// A value is read as is, and as if it were put inside a string literal between single or double quotes,
+/// the context giving the highest score being kept.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct SqlInjectionDetector;
+
+impl SqlInjectionDetector {
+    /// Lowest score of a reported value.
+    pub const REPORT_SCORE: f64 = 0.5;
+
+    /// Lowest score of a value reported with the high severity.
+    pub const HIGH_SEVERITY_SCORE: f64 = 0.8;
+
+    /// Quotes of the string literals a value may be put in.
+    const QUOTES: [char; 2] = ['\'', '"'];
+
+    /// Keywords starting a statement after a semicolon.
+    const STATEMENT_KEYWORDS: [&'static str; 13] = [
+        "SELECT", "INSERT", "UPDATE", "DELETE", "DROP", "TRUNCATE", "ALTER", "CREATE", "EXEC",
+        "EXECUTE", "DECLARE", "SHUTDOWN", "WAITFOR",
+    ];
+
+    /// Keywords expected in the statement following a semicolon, other than the one starting it.
+    const CLAUSE_KEYWORDS: [&'static str; 12] = [
+        "FROM",
+        "INTO",
+        "TABLE",
+        "DATABASE",
+        "SET",
+        "WHERE",
+        "VALUES",
+        "DELAY",
+        "USER",
+        "LOGIN",
+        "COLUMN",
+        "XP_CMDSHELL",
+    ];
+
+    /// Keywords continuing a query after a quote closing a string literal.
+    const CONTINUATION_KEYWORDS: [&'static str; 13] = [
+        "OR", "AND", "XOR", "UNION", "SELECT", "WHERE", "HAVING", "GROUP", "ORDER", "LIKE", "IS",
+        "IN", "LIMIT",
+    ];
+
+    /// Keywords of the bare SQL keyword rule, only a weak signal.
+    const SQL_KEYWORDS: [&'static str; 15] = [
+        "SELECT", "INSERT", "UPDATE", "DELETE", "DROP", "TRUNCATE", "EXEC", "UNION", "ALTER",
+        "CREATE", "REPLACE", "MERGE", "CALL", "DECLARE", "CAST",
+    ];
+
+    /// Return the score of the value, from 0 to 1, and the signals found with the fragment of the value matching each,
+    /// in the context where the value is the most dangerous.
+    #[must_use]
+    pub fn score(value: &str) -> (f64, Vec<(SqlSignal, String)>) {
+        let mut best: (f64, Vec<(SqlSignal, String)>) = Self::score_in_context(value, None);
+
+        for quote in Self::QUOTES {
+            if value.contains(quote) {
+                let scored: (f64, Vec<(SqlSignal, String)>) =
+                    Self::score_in_context(value, Some(quote));
+                if scored.0 > best.0 {
+                    best = scored;
+                }
+            }
+        }
+
+        best
+    }
+
+    /// Return the score of the value and its signals, read as is or inside a string literal between the given quotes.
+    fn score_in_context(value: &str, quote: Option<char>) -> (f64, Vec<(SqlSignal, String)>) {
+        let source: String = quote.map_or_else(
+            || String::from(value),
+            |quote| format!("{quote}{value}{quote}"),
+        );
+        let tokens: Vec<SqlToken> = SqlToken::tokenize(&source);
+
+        // A literal left open breaks the query, the value cannot be injected in this context
+        if quote.is_some()
+            && tokens
+                .iter()
+                .any(|token| token.kind == SqlTokenKind::UnterminatedString)
+        {
+            return (0.0, Vec::new());
+        }
+
+        let mut signals: Vec<(SqlSignal, usize, usize)> = Vec::new();
+
+        let mut quote_break: bool = false;
+        if quote.is_some()
+            && let [first, next, ..] = tokens.as_slice()
+            && Self::continues_query(next, &source)
+        {
+            quote_break = true;
+            signals.push((SqlSignal::QuoteBreak, first.end - 1, next.end));
+        }
+
+        for (index, token) in tokens.iter().enumerate() {
+            let rest: &[SqlToken] = &tokens[index + 1..];
+            let word: String = token.text(&source).to_ascii_uppercase();
+
+            let signal: Option<(SqlSignal, usize)> =
+                match token.kind {
+                    SqlTokenKind::Comment => Some((SqlSignal::Comment, token.end)),
+                    SqlTokenKind::Semicolon => Self::stacked_query_end(rest, &source)
+                        .map(|end| (SqlSignal::StackedQuery, end)),
+                    SqlTokenKind::Word if word == "UNION" => Self::union_select_end(rest, &source)
+                        .map(|end| (SqlSignal::UnionSelect, end)),
+                    SqlTokenKind::Word | SqlTokenKind::Operator
+                        if matches!(word.as_str(), "OR" | "AND" | "||" | "&&") =>
+                    {
+                        Self::tautology_end(rest, &source, quote_break && index == 1)
+                            .map(|end| (SqlSignal::Tautology, end))
+                    }
+                    _ => None,
+                };
+
+            if let Some((signal, end)) = signal {
+                signals.push((signal, token.start, end));
+            } else if token.kind == SqlTokenKind::Word
+                && Self::SQL_KEYWORDS.contains(&word.as_str())
+            {
+                signals.push((SqlSignal::Keyword, token.start, token.end));
+            }
+        }
+
+        // Each signal counts once, with the fragment of its first occurrence
+        let offset: usize = quote.map_or(0, char::len_utf8);
+        let mut found: Vec<(SqlSignal, String)> = Vec::new();
+        for (signal, start, end) in signals {
+            if found.iter().all(|(seen, _)| *seen != signal) {
+                let start: usize = start.saturating_sub(offset).min(value.len());
+                let end: usize = end.saturating_sub(offset).min(value.len());
+                found.push((signal, String::from(value[start..end].trim())));
+            }
+        }
+
+        // The sum is rounded so that the weights adding up to a threshold reach it
+        let score: f64 = found
+            .iter()
+            .map(|(signal, _)| signal.weight())
+            .sum::<f64>()
+            .min(1.0);
+        ((score * 100.0).round() / 100.0, found)
+    }
+
+    /// Return true if the token continues a query after a string literal.
+    fn continues_query(token: &SqlToken, source: &str) -> bool {
+        match token.kind {
+            SqlTokenKind::Comment | SqlTokenKind::Semicolon | SqlTokenKind::Operator => true,
+            SqlTokenKind::Parenthesis => token.text(source) == ")",
+            SqlTokenKind::Word => Self::CONTINUATION_KEYWORDS
+                .contains(&token.text(source).to_ascii_uppercase().as_str()),
+            _ => false,
+        }
+    }
+
+    /// Return the end of the statement following a semicolon, if it starts with a statement keyword
+    /// and has a clause keyword or a comment, so that a semicolon in a sentence is not enough.
+    fn stacked_query_end(rest: &[SqlToken], source: &str) -> Option<usize> {
+        let first: &SqlToken = rest.first()?;
+        if !Self::STATEMENT_KEYWORDS.contains(&first.text(source).to_ascii_uppercase().as_str()) {
+            return None;
+        }
+
+        rest[1..]
+            .iter()
+            .take_while(|token| token.kind != SqlTokenKind::Semicolon)
+            .find(|token| {
+                token.kind == SqlTokenKind::Comment
+                    || Self::CLAUSE_KEYWORDS
+                        .contains(&token.text(source).to_ascii_uppercase().as_str())
+            })
+            .map(|token| token.end)
+    }
+
+    /// Return the end of the `SELECT` following `UNION`, `UNION ALL` or `UNION DISTINCT`, with an optional parenthesis.
+    fn union_select_end(rest: &[SqlToken], source: &str) -> Option<usize> {
+        rest.iter()
+            .take(3)
+            .map(|token| (token, token.text(source).to_ascii_uppercase()))
+            .take_while(|(_, text)| matches!(text.as_str(), "ALL" | "DISTINCT" | "(" | "SELECT"))
+            .find(|(_, text)| text == "SELECT")
+            .map(|(token, _)| token.end)
+    }
+
+    /// Return the end of the condition following `OR` or `AND` if it is always true:
+    /// two equal operands compared, two numbers whose comparison holds, or a number other than 0 or `TRUE` alone.
+    /// An operand alone only counts before a comment or a semicolon, or right after a quote break,
+    /// so that "1 or 2" or "Room 4 and 5" is not read as a condition.
+    fn tautology_end(rest: &[SqlToken], source: &str, after_quote_break: bool) -> Option<usize> {
+        let left: &SqlToken = rest.first()?;
+        let left_value: String = Self::operand_value(left, source)?;
+
+        match rest.get(1..3) {
+            Some([operator, right])
+                if operator.kind == SqlTokenKind::Operator
+                    || operator.text(source).eq_ignore_ascii_case("LIKE") =>
+            {
+                let right_value: String = Self::operand_value(right, source)?;
+                let operator: String = operator.text(source).to_ascii_uppercase();
+
+                let holds: bool = match (left_value.parse::<f64>(), right_value.parse::<f64>()) {
+                    (Ok(left_number), Ok(right_number)) => match operator.as_str() {
+                        "=" | "==" | "LIKE" => left_number == right_number,
+                        "<>" | "!=" => left_number != right_number,
+                        "<" => left_number < right_number,
+                        ">" => left_number > right_number,
+                        "<=" => left_number <= right_number,
+                        ">=" => left_number >= right_number,
+                        _ => false,
+                    },
+                    _ => {
+                        matches!(operator.as_str(), "=" | "==" | "LIKE")
+                            && left_value == right_value
+                    }
+                };
+                holds.then_some(right.end)
+            }
+            _ => {
+                let ends_condition: bool = match rest.get(1) {
+                    Some(token)
+                        if matches!(
+                            token.kind,
+                            SqlTokenKind::Comment | SqlTokenKind::Semicolon
+                        ) =>
+                    {
+                        true
+                    }
+                    Some(token) => after_quote_break && token.kind == SqlTokenKind::Parenthesis,
+                    None => after_quote_break,
+                };
+                let always_true: bool = left_value == "TRUE"
+                    || left_value.parse::<f64>().is_ok_and(|number| number != 0.0);
+                (ends_condition && always_true).then_some(left.end)
+            }
+        }
+    }
+
+    /// Return the value of an operand of a comparison: the content of a string literal,
+    /// a number or a word in upper case, or `None` for the other tokens and the keywords.
+    fn operand_value(token: &SqlToken, source: &str) -> Option<String> {
+        let text: &str = token.text(source);
+        match token.kind {
+            SqlTokenKind::String => Some(text[1..text.len() - 1].to_string()),
+            SqlTokenKind::Number => Some(String::from(text)),
+            SqlTokenKind::Word => {
+                let word: String = text.to_ascii_uppercase();
+                (!Self::CONTINUATION_KEYWORDS.contains(&word.as_str())
+                    && !Self::STATEMENT_KEYWORDS.contains(&word.as_str()))
+                .then_some(word)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Detector for SqlInjectionDetector {
+    fn detect(&self, value: &str) -> Option<Detection> {
+        // Values without spaces nor SQL punctuation are single words or numbers
+        if !value.contains(|character: char| {
+            character.is_whitespace() || "'\";-#/=<>|&".contains(character)
+        }) {
+            return None;
+        }
+
+        let (score, signals): (f64, Vec<(SqlSignal, String)>) = Self::score(value);
+        if score < Self::REPORT_SCORE {
+            return None;
+        }
+
+        let severity: Severity = if score >= Self::HIGH_SEVERITY_SCORE {
+            Severity::High
+        } else {
+            Severity::Medium
+        };
+        let reasons: Vec<String> = signals
+            .iter()
+            .flat_map(|(signal, fragment)| {
+                [
+                    format!("{}:{}", DetectionSource::Sql.as_str(), signal.as_str()),
+                    format!("fragment:{fragment}"),
+                ]
+            })
+            .collect();
+
+        let mut detection: Detection = Detection::new(DetectionSource::Sql, reasons, severity);
+        detection.score = score;
+        detection.threshold = Self::REPORT_SCORE;
+        Some(detection)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signals(value: &str) -> Vec<SqlSignal> {
+        SqlInjectionDetector::score(value)
+            .1
+            .iter()
+            .map(|(signal, _)| *signal)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_sql_injections() {
+        assert_eq!(
+            signals("' OR 1=1 --"),
+            [
+                SqlSignal::QuoteBreak,
+                SqlSignal::Tautology,
+                SqlSignal::Comment
+            ]
+        );
+        assert_eq!(
+            signals("' or 'a'='a"),
+            [SqlSignal::QuoteBreak, SqlSignal::Tautology]
+        );
+        assert_eq!(
+            signals("admin'--"),
+            [SqlSignal::QuoteBreak, SqlSignal::Comment]
+        );
+        assert_eq!(
+            signals("1; DROP TABLE users"),
+            [SqlSignal::StackedQuery, SqlSignal::Keyword]
+        );
+        assert_eq!(
+            signals("0 UNION ALL SELECT password FROM users"),
+            [SqlSignal::UnionSelect, SqlSignal::Keyword]
+        );
+        assert_eq!(signals("x\") OR 2>1 #"), signals("x') OR 2>1 #"));
+        assert_eq!(
+            signals("x' OR 1) AND ('a"),
+            [SqlSignal::QuoteBreak, SqlSignal::Tautology]
+        );
+        assert_eq!(
+            signals("4 and 5 --"),
+            [SqlSignal::Tautology, SqlSignal::Comment]
+        );
+        assert!(signals("Room 4 and 5").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_business_text() {
+        const BUSINESS_TEXT: [&str; 9] = [
+            "Create account",
+            "Replace part; update the order",
+            "O'Reilly or Smith",
+            "It's done -- see the notes",
+            "Tom or Jerry = cartoon",
+            "Drop-off at 5",
+            "\"Quoted\" title",
+            "1 or 2",
+            "Room 4 and 5",
+        ];
+
+        for value in &BUSINESS_TEXT {
+            assert!(
+                SqlInjectionDetector.detect(value).is_none(),
+                "Error on : {value}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sql_detection() {
+        let detection: Detection = SqlInjectionDetector.detect("' OR 1=1 --").unwrap();
+        assert_eq!(detection.source, DetectionSource::Sql);
+        assert_eq!(detection.severity, Severity::High);
+        assert!((detection.score - 1.0).abs() < f64::EPSILON);
+        assert_eq!(
+            detection.reasons,
+            [
+                "sql:quote_break",
+                "fragment:' OR",
+                "sql:tautology",
+                "fragment:OR 1=1",
+                "sql:comment",
+                "fragment:--"
+            ]
+        );
+
+        assert_eq!(
+            SqlInjectionDetector
+                .detect("1; DROP TABLE users")
+                .unwrap()
+                .severity,
+            Severity::High
+        );
+
+        let detection: Detection = SqlInjectionDetector.detect("admin'--").unwrap();
+        assert_eq!(detection.severity, Severity::Medium);
+        assert!((detection.score - 0.7).abs() < 1e-9);
+    }
+}
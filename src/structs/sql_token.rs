@@ -0,0 +1,177 @@
+use crate::enums::sql_token_kind::SqlTokenKind;
+
+/// Represents a token of a value read as SQL, with its byte range in the value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SqlToken {
+    pub kind: SqlTokenKind,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl SqlToken {
+    /// Bytes of the comparison and logical operators.
+    const OPERATOR_BYTES: &'static [u8] = b"=<>!|&";
+
+    /// Create a new instance of `SqlToken`.
+    #[inline]
+    #[must_use]
+    pub const fn new(kind: SqlTokenKind, start: usize, end: usize) -> Self {
+        Self { kind, start, end }
+    }
+
+    /// Return the text of the token in the value it was read from.
+    #[inline]
+    #[must_use]
+    pub fn text<'a>(&self, source: &'a str) -> &'a str {
+        &source[self.start..self.end]
+    }
+
+    /// Split a value into SQL tokens, leaving the spaces out.
+    /// The value is read byte by byte: the characters outside ASCII belong to the words, so that each range
+    /// starts and ends on a character boundary.
+    #[must_use]
+    pub fn tokenize(source: &str) -> Vec<Self> {
+        let bytes: &[u8] = source.as_bytes();
+        let mut tokens: Vec<Self> = Vec::new();
+        let mut index: usize = 0;
+
+        while index < bytes.len() {
+            let start: usize = index;
+            let byte: u8 = bytes[index];
+            let next: Option<u8> = bytes.get(index + 1).copied();
+
+            let kind: SqlTokenKind = match byte {
+                _ if byte.is_ascii_whitespace() => {
+                    index += 1;
+                    continue;
+                }
+                b'\'' | b'"' | b'`' => {
+                    let (end, closed): (usize, bool) = Self::literal_end(bytes, index);
+                    index = end;
+                    if closed {
+                        SqlTokenKind::String
+                    } else {
+                        SqlTokenKind::UnterminatedString
+                    }
+                }
+                b'-' if next == Some(b'-') => {
+                    index = bytes.len();
+                    SqlTokenKind::Comment
+                }
+                b'#' => {
+                    index = bytes.len();
+                    SqlTokenKind::Comment
+                }
+                b'/' if next == Some(b'*') => {
+                    index = source[index + 2..]
+                        .find("*/")
+                        .map_or(bytes.len(), |end| index + end + 4);
+                    SqlTokenKind::Comment
+                }
+                b';' => {
+                    index += 1;
+                    SqlTokenKind::Semicolon
+                }
+                b'(' | b')' => {
+                    index += 1;
+                    SqlTokenKind::Parenthesis
+                }
+                b'0'..=b'9' => {
+                    index = Self::run_end(bytes, index, |byte| {
+                        byte.is_ascii_alphanumeric() || byte == b'.'
+                    });
+                    SqlTokenKind::Number
+                }
+                _ if Self::is_word_byte(byte) => {
+                    index = Self::run_end(bytes, index, Self::is_word_byte);
+                    SqlTokenKind::Word
+                }
+                _ if Self::OPERATOR_BYTES.contains(&byte) => {
+                    index =
+                        Self::run_end(bytes, index, |byte| Self::OPERATOR_BYTES.contains(&byte))
+                            .min(index + 2);
+                    SqlTokenKind::Operator
+                }
+                _ => {
+                    index += 1;
+                    SqlTokenKind::Other
+                }
+            };
+
+            tokens.push(Self::new(kind, start, index));
+        }
+
+        tokens
+    }
+
+    /// Return the end of the string literal starting at the given quote and whether it is closed,
+    /// a doubled quote escaping a quote inside the literal.
+    fn literal_end(bytes: &[u8], start: usize) -> (usize, bool) {
+        let quote: u8 = bytes[start];
+        let mut index: usize = start + 1;
+
+        while index < bytes.len() {
+            if bytes[index] == quote {
+                if bytes.get(index + 1) == Some(&quote) {
+                    index += 2;
+                    continue;
+                }
+                return (index + 1, true);
+            }
+            index += 1;
+        }
+
+        (bytes.len(), false)
+    }
+
+    /// Return the end of the run of bytes accepted by the predicate, starting at the given index.
+    fn run_end(bytes: &[u8], start: usize, accepts: impl Fn(u8) -> bool) -> usize {
+        bytes[start..]
+            .iter()
+            .position(|&byte| !accepts(byte))
+            .map_or(bytes.len(), |length| start + length)
+    }
+
+    /// Return true if the byte belongs to a keyword or an identifier.
+    const fn is_word_byte(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || matches!(byte, b'_' | b'@' | b'$') || !byte.is_ascii()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds_and_texts(source: &str) -> Vec<(SqlTokenKind, &str)> {
+        SqlToken::tokenize(source)
+            .iter()
+            .map(|token| (token.kind, token.text(source)))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_tokenize() {
+        assert_eq!(
+            kinds_and_texts("'' OR 'it''s'<>1.5;--'"),
+            [
+                (SqlTokenKind::String, "''"),
+                (SqlTokenKind::Word, "OR"),
+                (SqlTokenKind::String, "'it''s'"),
+                (SqlTokenKind::Operator, "<>"),
+                (SqlTokenKind::Number, "1.5"),
+                (SqlTokenKind::Semicolon, ";"),
+                (SqlTokenKind::Comment, "--'")
+            ]
+        );
+        assert_eq!(
+            kinds_and_texts("Hélène /* x */ ) \"open"),
+            [
+                (SqlTokenKind::Word, "Hélène"),
+                (SqlTokenKind::Comment, "/* x */"),
+                (SqlTokenKind::Parenthesis, ")"),
+                (SqlTokenKind::UnterminatedString, "\"open")
+            ]
+        );
+    }
+}
@@ -0,0 +1,417 @@
+use crate::enums::structure_issue::StructureIssue;
+use crate::structs::field_region::FieldRegion;
+use crate::structs::structure_finding::StructureFinding;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, Read};
+
+/// Checks the structure of CSV data while it is read: each byte given to the CSV reader goes through a small
+/// parser of its own, so that the problems the reader hides or rejects are found in the same pass,
+/// even for the standard input which can only be read once.
+/// Records are split like the CSV reader does: fields are separated by the separator, quoted with `"`,
+/// a doubled quote escaping a quote inside a quoted field.
+/// The region of each field is kept until the CSV reader returns its record, see `take_field_regions`.
+#[non_exhaustive]
+pub struct StructureChecker<R: Read> {
+    inner: R,
+    separator: u8,
+    findings: Vec<StructureFinding>,
+    line: u32,
+    column: u32, // Column of the next byte, in characters
+    record_line: u32,
+    field_start: Option<(u32, u32)>,
+    field_end: (u32, u32),
+    record_regions: Vec<FieldRegion>,
+    field_regions: VecDeque<(u32, Vec<FieldRegion>)>, // Regions of the fields of each ended record, by start line
+    header_count: Option<usize>,
+    header_fields: Vec<Vec<u8>>,
+    field: Vec<u8>,
+    field_count: usize,
+    record_len: usize,
+    in_quotes: bool,
+    after_quote: bool,
+    stray_quote: bool,
+    has_nul: bool,
+    last_field_empty: bool,
+}
+
+impl<R: Read> StructureChecker<R> {
+    /// Quote character of the fields.
+    const QUOTE: u8 = b'"';
+
+    /// UTF-8 encoding of the byte order mark.
+    const BOM: &'static [u8] = b"\xEF\xBB\xBF";
+
+    /// Wrap a reader of UTF-8 CSV data separated by the given separator.
+    #[must_use]
+    pub fn new(inner: R, separator: u8) -> Self {
+        Self {
+            inner,
+            separator,
+            findings: Vec::new(),
+            line: 1,
+            column: 1,
+            record_line: 1,
+            field_start: None,
+            field_end: (1, 1),
+            record_regions: Vec::new(),
+            field_regions: VecDeque::new(),
+            header_count: None,
+            header_fields: Vec::new(),
+            field: Vec::new(),
+            field_count: 0,
+            record_len: 0,
+            in_quotes: false,
+            after_quote: false,
+            stray_quote: false,
+            has_nul: false,
+            last_field_empty: false,
+        }
+    }
+
+    /// Return the regions of the fields of the record starting at the given line, once the CSV reader returned it,
+    /// forgetting the regions of the records before it.
+    pub fn take_field_regions(&mut self, line: u32) -> Option<Vec<FieldRegion>> {
+        while let Some((record_line, _)) = self.field_regions.front()
+            && *record_line < line
+        {
+            self.field_regions.pop_front();
+        }
+
+        match self.field_regions.front() {
+            Some((record_line, _)) if *record_line == line => {
+                self.field_regions.pop_front().map(|(_, regions)| regions)
+            }
+            // The last record of data without a final line break is not ended yet
+            _ if self.record_line == line => {
+                let mut regions: Vec<FieldRegion> = self.record_regions.clone();
+                regions.push(self.field_region());
+                Some(regions)
+            }
+            _ => None,
+        }
+    }
+
+    /// Check the end of the data and return the structural findings, in the order of their lines.
+    #[must_use]
+    pub fn finish(mut self) -> Vec<StructureFinding> {
+        if self.in_quotes && !self.after_quote {
+            self.push(
+                StructureIssue::UnbalancedQuote,
+                String::from("quoted field not closed before the end of the file"),
+            );
+        }
+        if self.record_len > 0 || self.field_count > 0 {
+            self.end_record();
+        }
+
+        self.findings.sort_by_key(|finding| finding.line);
+        self.findings
+    }
+
+    /// Go through the bytes read, keeping the state of the current record between calls.
+    fn check(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.check_byte(byte);
+
+            // The columns count the characters, not the continuation bytes of their UTF-8 encoding
+            if byte == b'\n' {
+                self.column = 1;
+            } else if !Self::is_continuation(byte) {
+                self.column += 1;
+            }
+        }
+    }
+
+    /// Go through a byte of the current record.
+    fn check_byte(&mut self, byte: u8) {
+        if self.in_quotes {
+            if byte == Self::QUOTE {
+                // A quote following a quote is escaped, otherwise it may close the field
+                if self.after_quote {
+                    self.add_byte(byte);
+                } else {
+                    self.extend_field(byte);
+                }
+                self.after_quote = !self.after_quote;
+                return;
+            }
+            if !self.after_quote {
+                self.add_byte(byte);
+                if byte == b'\n' {
+                    self.line += 1;
+                }
+                return;
+            }
+            // The quote closed the field, the byte is read as if it were outside quotes
+            self.in_quotes = false;
+        }
+
+        match byte {
+            b'\n' => {
+                self.end_record();
+                self.line += 1;
+                self.record_line = self.line;
+            }
+            b'\r' => {}
+            _ if byte == self.separator => self.end_field(),
+            Self::QUOTE if self.field.is_empty() && !self.after_quote => {
+                self.in_quotes = true;
+                self.record_len += 1;
+                self.extend_field(byte);
+            }
+            _ => {
+                if byte == Self::QUOTE || self.after_quote {
+                    self.stray_quote = true;
+                }
+                self.add_byte(byte);
+            }
+        }
+    }
+
+    /// Return true if the byte continues the UTF-8 encoding of a character.
+    const fn is_continuation(byte: u8) -> bool {
+        byte & 0xC0 == 0x80
+    }
+
+    /// Extend the region of the current field up to the character starting with the byte.
+    fn extend_field(&mut self, byte: u8) {
+        if Self::is_continuation(byte) {
+            return;
+        }
+        if self.field_start.is_none() {
+            self.field_start = Some((self.line, self.column));
+        }
+        self.field_end = (self.line, self.column + 1);
+    }
+
+    /// Return the region of the current field, an empty field lying where it ends.
+    fn field_region(&self) -> FieldRegion {
+        match self.field_start {
+            Some(start) => FieldRegion::new(start, self.field_end),
+            None => FieldRegion::new((self.line, self.column), (self.line, self.column)),
+        }
+    }
+
+    /// Add a byte to the current field.
+    fn add_byte(&mut self, byte: u8) {
+        self.extend_field(byte);
+        self.has_nul |= byte == 0;
+        self.record_len += 1;
+        self.field.push(byte);
+    }
+
+    /// End the current field, keeping its content only for the header.
+    fn end_field(&mut self) {
+        self.last_field_empty = self.field.is_empty() && !self.after_quote;
+        self.field_count += 1;
+        self.after_quote = false;
+        let region: FieldRegion = self.field_region();
+        self.record_regions.push(region);
+        self.field_start = None;
+
+        if self.header_count.is_none() {
+            self.header_fields.push(std::mem::take(&mut self.field));
+        } else {
+            self.field.clear();
+        }
+    }
+
+    /// End the current record and check it, a record without any byte being a blank line.
+    fn end_record(&mut self) {
+        if self.record_len == 0 && self.field_count == 0 {
+            self.push(StructureIssue::BlankLine, String::from("blank line"));
+            return;
+        }
+        self.end_field();
+
+        if self.stray_quote {
+            self.push(
+                StructureIssue::UnbalancedQuote,
+                String::from("quote inside an unquoted field or after a closing quote"),
+            );
+        }
+        if self.has_nul {
+            self.push(
+                StructureIssue::EmbeddedNul,
+                String::from("NUL character inside a field"),
+            );
+        }
+
+        match self.header_count {
+            None => self.check_headers(),
+            Some(header_count) if self.field_count != header_count => {
+                if self.field_count == header_count + 1 && self.last_field_empty {
+                    self.push(
+                        StructureIssue::TrailingDelimiter,
+                        String::from("row ending with a delimiter"),
+                    );
+                } else {
+                    self.push(
+                        StructureIssue::RaggedRow,
+                        format!("{} fields, {header_count} expected", self.field_count),
+                    );
+                }
+            }
+            Some(_) => {}
+        }
+
+        let regions: Vec<FieldRegion> = std::mem::take(&mut self.record_regions);
+        self.field_regions.push_back((self.record_line, regions));
+        self.field_count = 0;
+        self.record_len = 0;
+        self.stray_quote = false;
+        self.has_nul = false;
+    }
+
+    /// Check the names of the headers once the header record is read.
+    fn check_headers(&mut self) {
+        let header_fields: Vec<Vec<u8>> = std::mem::take(&mut self.header_fields);
+        self.header_count = Some(header_fields.len());
+
+        let mut first_indexes: HashMap<String, usize> = HashMap::new();
+        for (index, field) in header_fields.iter().enumerate() {
+            let name: String = String::from_utf8_lossy(field).trim().to_string();
+
+            if field
+                .windows(Self::BOM.len())
+                .any(|window| window == Self::BOM)
+            {
+                self.push_in_column(
+                    StructureIssue::BomInHeader,
+                    &name,
+                    format!("byte order mark inside the header of column {index}"),
+                );
+            }
+
+            if name.is_empty() && index + 1 == header_fields.len() && index > 0 {
+                self.push(
+                    StructureIssue::TrailingDelimiter,
+                    String::from("header ending with a delimiter"),
+                );
+            } else if let Some(first_index) = first_indexes.get(&name) {
+                self.push_in_column(
+                    StructureIssue::DuplicateHeader,
+                    &name,
+                    format!("column {index} has the same header as column {first_index}"),
+                );
+            } else {
+                first_indexes.insert(name, index);
+            }
+        }
+    }
+
+    /// Record a finding at the start line of the current record.
+    fn push(&mut self, issue: StructureIssue, message: String) {
+        self.findings
+            .push(StructureFinding::new(issue, self.record_line, message));
+    }
+
+    /// Record a finding in a column at the start line of the current record.
+    fn push_in_column(&mut self, issue: StructureIssue, column: &str, message: String) {
+        let mut finding: StructureFinding = StructureFinding::new(issue, self.record_line, message);
+        finding.column = Some(String::from(column));
+        self.findings.push(finding);
+    }
+}
+
+impl<R: Read> Read for StructureChecker<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read: usize = self.inner.read(buf)?;
+        self.check(&buf[..read]);
+        Ok(read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(content: &str) -> Vec<(StructureIssue, u32)> {
+        let mut checker: StructureChecker<&[u8]> = StructureChecker::new(content.as_bytes(), b',');
+        io::copy(&mut checker, &mut io::sink()).unwrap();
+        checker
+            .finish()
+            .iter()
+            .map(|finding| (finding.issue, finding.line))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_well_formed_file() {
+        assert!(
+            check("Name,Comment\nJohn,\"Hello, \"\"world\"\"\"\nJane,\"two\nlines\"\n").is_empty()
+        );
+        assert!(check("Name,Comment\r\nJohn,\"\"\r\n").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rows_and_lines() {
+        assert_eq!(
+            check("Name,Comment\nJohn\n\nJane,hi,\nBob,a,b,c\n"),
+            [
+                (StructureIssue::RaggedRow, 2),
+                (StructureIssue::BlankLine, 3),
+                (StructureIssue::TrailingDelimiter, 4),
+                (StructureIssue::RaggedRow, 5)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_quotes_and_nul() {
+        assert_eq!(
+            check("Name,Comment\nJo\"hn,hi\n\"Jane\"x,hi\nBob,a\0b\nAlice,\"open\n"),
+            [
+                (StructureIssue::UnbalancedQuote, 2),
+                (StructureIssue::UnbalancedQuote, 3),
+                (StructureIssue::EmbeddedNul, 4),
+                (StructureIssue::UnbalancedQuote, 5)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_field_regions() {
+        let content: &str = "Name,Comment\r\nJosé,\"two\nlines\"\r\n,x\nBob,last";
+        let mut checker: StructureChecker<&[u8]> = StructureChecker::new(content.as_bytes(), b',');
+        io::copy(&mut checker, &mut io::sink()).unwrap();
+
+        let regions: Vec<FieldRegion> = checker.take_field_regions(2).unwrap();
+        assert_eq!(
+            regions,
+            [
+                FieldRegion::new((2, 1), (2, 5)),
+                FieldRegion::new((2, 6), (3, 7))
+            ]
+        );
+        // An empty field lies where it ends
+        assert_eq!(
+            checker.take_field_regions(4).unwrap(),
+            [
+                FieldRegion::new((4, 1), (4, 1)),
+                FieldRegion::new((4, 2), (4, 3))
+            ]
+        );
+        assert_eq!(checker.take_field_regions(2), None);
+        // The last record has no final line break
+        assert_eq!(
+            checker.take_field_regions(5).unwrap(),
+            [
+                FieldRegion::new((5, 1), (5, 4)),
+                FieldRegion::new((5, 5), (5, 9))
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_headers() {
+        assert_eq!(
+            check("Id,\u{feff}Name,Id,\n1,a,2,\n"),
+            [
+                (StructureIssue::BomInHeader, 1),
+                (StructureIssue::DuplicateHeader, 1),
+                (StructureIssue::TrailingDelimiter, 1)
+            ]
+        );
+    }
+}
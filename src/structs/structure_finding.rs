@@ -0,0 +1,52 @@
+use crate::enums::color::Color;
+use crate::enums::severity::Severity;
+use crate::enums::structure_issue::StructureIssue;
+use serde::{Deserialize, Serialize};
+
+/// Represents a structural problem found at a line of a CSV file, reported apart from the anomalies of its values.
+/// The line is the physical line where the record starts, the line of the header being 1.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct StructureFinding {
+    pub issue: StructureIssue,
+    pub line: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<String>,
+    pub message: String,
+    pub severity: Severity,
+}
+
+impl StructureFinding {
+    /// Create a new instance of `StructureFinding`, with the severity of its issue.
+    #[inline]
+    #[must_use]
+    pub fn new(issue: StructureIssue, line: u32, message: String) -> Self {
+        Self {
+            issue,
+            line,
+            column: None,
+            message,
+            severity: issue.severity(),
+        }
+    }
+
+    /// Return a formatted string representation of the finding.
+    #[inline]
+    #[must_use]
+    pub fn as_str(&self) -> String {
+        let column: String = self
+            .column
+            .as_ref()
+            .map(|column| format!(", column {column:?}"))
+            .unwrap_or_default();
+
+        format!(
+            "{}[{}]{} Line {}{column}: {}",
+            Color::Yellow,
+            self.issue.as_str(),
+            Color::Reset,
+            self.line,
+            self.message
+        )
+    }
+}
@@ -1,9 +1,7 @@
-use crate::enums::log_level::LogLevel;
+use crate::enums::datalint_error::DataLintError;
 use crate::structs::inferable_value::InferableValue;
-use crate::structs::logger::print_message;
 use rayon::iter::IntoParallelRefIterator;
 use rayon::iter::ParallelIterator;
-use std::error::Error;
 use tokenizers::{Encoding, Tokenizer};
 
 /// Represents a tokenizer for the model, providing methods to encode and decode text data.
@@ -11,27 +9,29 @@ use tokenizers::{Encoding, Tokenizer};
 pub struct ModelTokenizer;
 
 impl ModelTokenizer {
+    /// Token ID used to pad the sequences, and to replace the masked tokens.
+    pub const PAD_TOKEN_ID: i64 = 0;
+
     /// Load the tokenizer from a configuration file.
-    pub fn from_config_file(file_path: &str) -> Result<Tokenizer, Box<dyn Error>> {
-        let tokenizer: Tokenizer = Tokenizer::from_file(file_path).unwrap_or_else(|e| {
-            print_message(
-                &format!("Erreur lors de la lecture du fichier de vocabulaire: {e}"),
-                &LogLevel::Error,
-            );
-            std::process::exit(1);
-        });
-        Ok(tokenizer)
+    pub fn from_config_file(file_path: &str) -> Result<Tokenizer, DataLintError> {
+        Tokenizer::from_file(file_path).map_err(|e| {
+            DataLintError::Tokenizer(format!("Error reading vocabulary file {file_path}: {e}"))
+        })
     }
 
     /// Encode the words from a batch of `InferableValue` into a vector of `Encoding` and returns the maximum sequence length.
     pub fn encode_words(
         tokenizer: &Tokenizer,
         batch_data: &[InferableValue],
-    ) -> (Vec<Encoding>, i64) {
+    ) -> Result<(Vec<Encoding>, i64), DataLintError> {
         let encodings: Vec<Encoding> = batch_data
             .iter()
-            .map(|data| tokenizer.encode(data.value.clone(), true).unwrap())
-            .collect();
+            .map(|data| {
+                tokenizer.encode(data.value.clone(), true).map_err(|e| {
+                    DataLintError::Tokenizer(format!("Error encoding {:?}: {e}", data.value))
+                })
+            })
+            .collect::<Result<_, _>>()?;
 
         let max_seq_length: i64 = encodings
             .iter()
@@ -39,7 +39,7 @@ impl ModelTokenizer {
             .max()
             .unwrap_or(0) as i64;
 
-        (encodings, max_seq_length)
+        Ok((encodings, max_seq_length))
     }
 
     /// Convert the IDs from an `Encoding` into a vector of `i64` and returns the sequence length.
@@ -56,7 +56,6 @@ impl ModelTokenizer {
     #[inline]
     #[must_use]
     pub fn build_tokens(encodings: &[Encoding], max_seq_length: i64) -> (Vec<i64>, Vec<i64>) {
-        const PAD_TOKEN_ID: i64 = 0;
         let batch_size: usize = encodings.len();
         let total_len: usize = batch_size * (usize::try_from(max_seq_length).unwrap_or_default());
 
@@ -71,7 +70,7 @@ impl ModelTokenizer {
                 .saturating_sub(seq_len);
 
             padded_ids.extend_from_slice(&ids);
-            padded_ids.resize(padded_ids.len() + pad_len, PAD_TOKEN_ID);
+            padded_ids.resize(padded_ids.len() + pad_len, Self::PAD_TOKEN_ID);
 
             attention_mask.extend(std::iter::repeat_n(1, seq_len));
             attention_mask.extend(std::iter::repeat_n(0, pad_len));
@@ -84,6 +83,8 @@ impl ModelTokenizer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::enums::log_level::LogLevel;
+    use crate::structs::logger::print_message;
     use std::path::PathBuf;
 
     #[tokio::test]
@@ -159,15 +160,11 @@ mod tests {
 
         let batch_data: Vec<InferableValue> = WORDS
             .iter()
-            .enumerate()
-            .map(|(i, &word)| InferableValue {
-                value: word.into(),
-                row_number: i,
-                column_index: 0,
-            })
+            .map(|&word| InferableValue::new(word.into(), 0, 0))
             .collect();
 
-        let (encodings, max_seq_length) = ModelTokenizer::encode_words(&tokenizer, &batch_data);
+        let (encodings, max_seq_length) =
+            ModelTokenizer::encode_words(&tokenizer, &batch_data).unwrap();
 
         assert_eq!(
             encodings.len(),
@@ -195,15 +192,11 @@ mod tests {
 
         let batch_data: Vec<InferableValue> = WORDS
             .iter()
-            .enumerate()
-            .map(|(i, &word)| InferableValue {
-                value: word.into(),
-                row_number: i,
-                column_index: 0,
-            })
+            .map(|&word| InferableValue::new(word.into(), 0, 0))
             .collect();
 
-        let (encodings, max_seq_length) = ModelTokenizer::encode_words(&tokenizer, &batch_data);
+        let (encodings, max_seq_length) =
+            ModelTokenizer::encode_words(&tokenizer, &batch_data).unwrap();
         let (padded_ids, attention_masks) =
             ModelTokenizer::build_tokens(&encodings, max_seq_length);
 
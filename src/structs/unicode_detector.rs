@@ -0,0 +1,233 @@
+use crate::enums::detection_source::DetectionSource;
+use crate::enums::script::Script;
+use crate::enums::severity::Severity;
+use crate::enums::unicode_issue::UnicodeIssue;
+use crate::structs::detection::Detection;
+use crate::traits::detector::Detector;
+
+/// Represents the detector of the characters hiding or disguising the text of a value: bidirectional controls,
+/// invisible and control characters, words mixing scripts and Cyrillic or Greek letters passing for Latin ones.
+/// Accented letters and text written in a single script, such as "Hélène" or "Москва", are not reported.
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub struct UnicodeDetector;
+
+impl UnicodeDetector {
+    /// Cyrillic and Greek letters looking like Latin letters.
+    const LATIN_LOOKALIKES: &'static str = "аеорсухіјѕԁԛԝһӏүАВЕКМНОРСТХЅІЈԚԜҮοαιρνκυΑΒΕΖΗΙΚΜΝΟΡΤΥΧ";
+
+    /// Return the Unicode issues of the value, each one with the first character showing it.
+    #[must_use]
+    pub fn issues(value: &str) -> Vec<(UnicodeIssue, char)> {
+        let mut issues: Vec<(UnicodeIssue, char)> = Vec::new();
+        let mut push = |issue: UnicodeIssue, character: char| {
+            if issues.iter().all(|(seen, _)| *seen != issue) {
+                issues.push((issue, character));
+            }
+        };
+
+        let characters: Vec<char> = value.chars().collect();
+        for (index, &character) in characters.iter().enumerate() {
+            let previous: Option<char> = index.checked_sub(1).map(|index| characters[index]);
+            let next: Option<char> = characters.get(index + 1).copied();
+
+            if Self::is_bidi(character, previous, next) {
+                push(UnicodeIssue::Bidi, character);
+            } else if character.is_control() && !matches!(character, '\t' | '\n' | '\r') {
+                push(UnicodeIssue::Control, character);
+            } else if Self::is_invisible(character, previous, next) {
+                push(UnicodeIssue::Invisible, character);
+            }
+        }
+
+        let has_latin: bool = value
+            .chars()
+            .any(|character| Script::of(character) == Some(Script::Latin));
+        for token in value.split_whitespace() {
+            let host_like: bool = Self::is_host_like(token);
+
+            for word in token.split(|character: char| {
+                !character.is_alphanumeric() && !Self::is_combining_mark(character)
+            }) {
+                let mut scripts: Vec<(Script, char)> = Vec::new();
+                for character in word.chars() {
+                    if let Some(script) = Script::of(character)
+                        && scripts.iter().all(|(seen, _)| *seen != script)
+                    {
+                        scripts.push((script, character));
+                    }
+                }
+
+                let lookalike: Option<char> = word
+                    .chars()
+                    .find(|&character| Self::LATIN_LOOKALIKES.contains(character));
+                let word_has_latin: bool =
+                    scripts.iter().any(|(script, _)| *script == Script::Latin);
+                let only_lookalikes: bool = word
+                    .chars()
+                    .all(|character| Self::LATIN_LOOKALIKES.contains(character));
+
+                match lookalike {
+                    // A look-alike letter among Latin letters, or a host or e-mail word spelled with look-alikes only
+                    // in Latin text, short words such as "с" or "και" being common in bilingual text
+                    Some(character)
+                        if word_has_latin || (only_lookalikes && host_like && has_latin) =>
+                    {
+                        push(UnicodeIssue::Confusable, character);
+                    }
+                    _ if scripts.len() > 1 => push(UnicodeIssue::MixedScript, scripts[1].1),
+                    _ => {}
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Return true if the token looks like a host name or an e-mail address, such as "аррӏе.com".
+    fn is_host_like(token: &str) -> bool {
+        let token: &str = token.trim_matches(|character: char| !character.is_alphanumeric());
+        token.contains(['@', '.'])
+    }
+
+    /// Return true if the character changes the direction of the text around it.
+    /// The direction marks are needed by Hebrew and Arabic text, so they are only reported
+    /// between ASCII characters or at an end of the value, like the zero-width joiners.
+    fn is_bidi(character: char, previous: Option<char>, next: Option<char>) -> bool {
+        match character {
+            '\u{200E}' | '\u{200F}' | '\u{061C}' => {
+                previous.is_none_or(|character| character.is_ascii())
+                    && next.is_none_or(|character| character.is_ascii())
+            }
+            '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => true,
+            _ => false,
+        }
+    }
+
+    /// Return true if the character has no glyph and is not needed by the text around it.
+    /// The zero-width joiners are needed by emoji sequences and many scripts, so they are only reported
+    /// between ASCII characters or at an end of the value.
+    fn is_invisible(character: char, previous: Option<char>, next: Option<char>) -> bool {
+        match character {
+            '\u{200C}' | '\u{200D}' => {
+                previous.is_none_or(|character| character.is_ascii())
+                    && next.is_none_or(|character| character.is_ascii())
+            }
+            '\u{200B}'
+            | '\u{2060}'..='\u{2064}'
+            | '\u{FEFF}'
+            | '\u{00AD}'
+            | '\u{034F}'
+            | '\u{180E}'
+            | '\u{115F}'
+            | '\u{1160}'
+            | '\u{17B4}'
+            | '\u{17B5}'
+            | '\u{3164}'
+            | '\u{FFA0}'
+            | '\u{E0000}'..='\u{E007F}' => true,
+            _ => false,
+        }
+    }
+
+    /// Return true if the character is a combining mark, such as the accent of a decomposed "é".
+    const fn is_combining_mark(character: char) -> bool {
+        matches!(
+            character,
+            '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE20}'..='\u{FE2F}'
+        )
+    }
+}
+
+impl Detector for UnicodeDetector {
+    fn detect(&self, value: &str) -> Option<Detection> {
+        // ASCII values can only hide control characters
+        if value.is_ascii()
+            && !value
+                .bytes()
+                .any(|byte| byte.is_ascii_control() && !matches!(byte, b'\t' | b'\n' | b'\r'))
+        {
+            return None;
+        }
+
+        let issues: Vec<(UnicodeIssue, char)> = Self::issues(value);
+        let severity: Severity = issues.iter().map(|(issue, _)| issue.severity()).max()?;
+        let reasons: Vec<String> = issues
+            .iter()
+            .flat_map(|(issue, character)| {
+                [
+                    format!("{}:{}", DetectionSource::Unicode.as_str(), issue.as_str()),
+                    format!("char:U+{:04X}", u32::from(*character)),
+                ]
+            })
+            .collect();
+
+        Some(Detection::new(DetectionSource::Unicode, reasons, severity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issues(value: &str) -> Vec<UnicodeIssue> {
+        UnicodeDetector::issues(value)
+            .iter()
+            .map(|(issue, _)| *issue)
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_unicode_issues() {
+        assert_eq!(issues("invoice\u{202E}fdp.exe"), [UnicodeIssue::Bidi]);
+        assert_eq!(issues("admin\u{200F}.exe"), [UnicodeIssue::Bidi]);
+        assert_eq!(issues("pay\u{200B}pal"), [UnicodeIssue::Invisible]);
+        assert_eq!(issues("red\u{1b}[31m"), [UnicodeIssue::Control]);
+        assert_eq!(issues("\u{85}next"), [UnicodeIssue::Control]);
+        assert_eq!(issues("pаypal@mail.com"), [UnicodeIssue::Confusable]);
+        assert_eq!(issues("login аррӏе.com"), [UnicodeIssue::Confusable]);
+        assert_eq!(issues("Ωmega"), [UnicodeIssue::MixedScript]);
+    }
+
+    #[tokio::test]
+    async fn test_legitimate_text() {
+        const TEXTS: [&str; 11] = [
+            "Hélène",
+            "He\u{301}le\u{300}ne",
+            "Москва",
+            "иван@почта.рф",
+            "Tokyo 東京",
+            "👨\u{200D}👩\u{200D}👧",
+            "Ελλάδα and Greece",
+            "Встреча с John о проекте у Anna",
+            "Η ομάδα και the team",
+            "שלום\u{200F} עולם",
+            "مرحبا\u{061C} بالعالم",
+        ];
+
+        for text in &TEXTS {
+            assert!(UnicodeDetector.detect(text).is_none(), "Error on : {text}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unicode_detection() {
+        let detection: Detection = UnicodeDetector.detect("pаypal\u{200D}").unwrap();
+        assert_eq!(detection.source, DetectionSource::Unicode);
+        assert_eq!(detection.severity, Severity::High);
+        assert_eq!(
+            detection.reasons,
+            [
+                "unicode:invisible",
+                "char:U+200D",
+                "unicode:confusable",
+                "char:U+0430"
+            ]
+        );
+        assert!(UnicodeDetector.detect("plain ascii").is_none());
+    }
+}
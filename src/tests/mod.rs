@@ -1,3 +1,6 @@
+#[cfg(test)]
+mod cli_tests;
+
 #[cfg(test)]
 mod csv_tests;
 
@@ -5,4 +8,4 @@ mod csv_tests;
 mod model_tests;
 
 #[cfg(test)]
-mod utils_tests;
\ No newline at end of file
+mod utils_tests;
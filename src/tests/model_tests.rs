@@ -1,4 +1,9 @@
+use csv::StringRecord;
+use datalib::structs::anomaly::Anomaly;
+use datalib::structs::inferable_value::InferableValue;
 use datalib::structs::model::Model;
+use datalib::structs::sql_injection_detector::SqlInjectionDetector;
+use datalib::traits::detector::Detector;
 use std::fs::File;
 use std::io::Write;
 
@@ -19,6 +24,109 @@ async fn test_from_config_file() {
     let model: Model = Model::from_config_file(CONFIG_JSON_PATH).unwrap();
     assert_eq!(model.model_path, "model.pb");
     assert_eq!(model.vocabulary_path, "vectorizer_data.json");
+    assert_eq!(model.threshold, Model::DEFAULT_THRESHOLD);
+    assert!(model.column_thresholds.is_empty());
+
+    if let Err(e) = std::fs::remove_file(CONFIG_JSON_PATH) {
+        eprintln!("Une erreur est survenue lors de la suppression du fichier: {e}");
+    }
+}
+
+#[tokio::test]
+async fn test_column_thresholds() {
+    const CONFIG_JSON: &str = r#"
+    {
+        "model_path": "model.pb",
+        "vocabulary_path": "vectorizer_data.json",
+        "threshold": 0.7,
+        "column_thresholds": {
+            "Comment": 0.95,
+            "Phone": 0.5
+        }
+    }
+    "#;
+    const CONFIG_JSON_PATH: &str = "test_column_thresholds.json";
+
+    let mut file: File = File::create(CONFIG_JSON_PATH).unwrap();
+    file.write_all(CONFIG_JSON.as_bytes()).unwrap();
+
+    let model: Model = Model::from_config_file(CONFIG_JSON_PATH).unwrap();
+    assert_eq!(model.threshold, 0.7);
+    assert_eq!(model.threshold_for("Comment"), 0.95);
+    assert_eq!(model.threshold_for("Phone"), 0.5);
+    assert_eq!(model.threshold_for("Name"), 0.7);
+    assert_eq!(model.lowest_threshold(), 0.5);
+
+    if let Err(e) = std::fs::remove_file(CONFIG_JSON_PATH) {
+        eprintln!("Une erreur est survenue lors de la suppression du fichier: {e}");
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_thresholds() {
+    const CONFIG_JSONS: [&str; 2] = [
+        r#"{"model_path": "model.pb", "vocabulary_path": "vocab.json", "threshold": 1.5}"#,
+        r#"{"model_path": "model.pb", "vocabulary_path": "vocab.json", "column_thresholds": {"Phone": -0.1}}"#,
+    ];
+    const CONFIG_JSON_PATH: &str = "test_invalid_thresholds.json";
+
+    for config_json in CONFIG_JSONS {
+        std::fs::write(CONFIG_JSON_PATH, config_json).unwrap();
+        assert!(Model::from_config_file(CONFIG_JSON_PATH).is_err());
+    }
+
+    if let Err(e) = std::fs::remove_file(CONFIG_JSON_PATH) {
+        eprintln!("Une erreur est survenue lors de la suppression du fichier: {e}");
+    }
+}
+
+#[tokio::test]
+async fn test_load_missing_model() {
+    const CONFIG_JSON: &str = r#"
+    {
+        "model_path": "missing_model.pt",
+        "vocabulary_path": "missing_tokenizer.json"
+    }
+    "#;
+    const CONFIG_JSON_PATH: &str = "test_load_missing_model.json";
+
+    let mut file: File = File::create(CONFIG_JSON_PATH).unwrap();
+    file.write_all(CONFIG_JSON.as_bytes()).unwrap();
+
+    let model: Model = Model::from_config_file(CONFIG_JSON_PATH).unwrap();
+    assert!(model.load().is_err());
+    assert!(model.score_values(&[]).unwrap().is_empty());
+
+    if let Err(e) = std::fs::remove_file(CONFIG_JSON_PATH) {
+        eprintln!("Une erreur est survenue lors de la suppression du fichier: {e}");
+    }
+}
+
+#[tokio::test]
+async fn test_detection_anomaly_threshold() {
+    const CONFIG_JSON: &str = r#"
+    {
+        "model_path": "model.pb",
+        "vocabulary_path": "vectorizer_data.json"
+    }
+    "#;
+    const CONFIG_JSON_PATH: &str = "test_detection_anomaly_threshold.json";
+
+    let mut file: File = File::create(CONFIG_JSON_PATH).unwrap();
+    file.write_all(CONFIG_JSON.as_bytes()).unwrap();
+    let model: Model = Model::from_config_file(CONFIG_JSON_PATH).unwrap();
+
+    // The SQL detector reports this value below the default threshold of the model, from its own threshold
+    let mut data: InferableValue = InferableValue::new(String::from("admin'--"), 2, 0);
+    data.detection = SqlInjectionDetector::default().detect(&data.value);
+    let anomaly: Anomaly = model
+        .detection_anomaly(&data, &StringRecord::from(vec!["Name"]))
+        .unwrap();
+
+    assert!((anomaly.score - 0.7).abs() < 1e-6);
+    assert!(f64::from(anomaly.score) < model.threshold_for("Name"));
+    assert!((f64::from(anomaly.threshold) - SqlInjectionDetector::REPORT_SCORE).abs() < 1e-6);
+    assert!(anomaly.score >= anomaly.threshold);
 
     if let Err(e) = std::fs::remove_file(CONFIG_JSON_PATH) {
         eprintln!("Une erreur est survenue lors de la suppression du fichier: {e}");
@@ -1,34 +1,15 @@
 use crate::tests::csv_tests::csv_utils::generate_csv_file;
+use datalib::enums::output_format::OutputFormat;
 use datalib::structs::anomaly::Anomaly;
+use datalib::structs::batch_summary::{BatchSummary, FileSummary};
+use datalib::structs::csv_file::CsvFile;
 use datalib::structs::json_output::JsonOutput;
-use datalib::utils::util::{file_exists, generate_json_file, get_file_from_args, get_file_name};
-
-#[tokio::test]
-async fn test_get_file_from_args() {
-    const FILE_NAME: &str = "test_get_file_from_args.csv";
-    const TEST: &str = "test";
-    const JSON_FILE: &str = r"\Test\Example.json";
-
-    generate_csv_file(FILE_NAME, b',');
-
-    let args_1: Vec<String> = vec![FILE_NAME.into()];
-    let args_2: Vec<String> = vec![TEST.into(), FILE_NAME.into()];
-    let args_3: Vec<String> = vec![TEST.into(), FILE_NAME.into(), JSON_FILE.into()];
-    let args_4: Vec<String> = vec![TEST.into(), TEST.into(), TEST.into(), FILE_NAME.into()];
-    let args_5: Vec<String> = vec![TEST.into(), JSON_FILE.into(), JSON_FILE.into()];
-
-    assert!(get_file_from_args(&args_1).is_err());
-    assert!(get_file_from_args(&args_2).is_err());
-    assert!(get_file_from_args(&args_3).is_ok());
-    assert!(get_file_from_args(&args_4).is_err());
-    assert!(get_file_from_args(&args_5).is_err());
-
-    let result: [String; 2] = get_file_from_args(&args_3).unwrap();
-    assert_eq!(result[0], FILE_NAME);
-    assert_eq!(result[1], "Example");
-
-    delete_file(FILE_NAME);
-}
+use datalib::utils::batch::{batch_output_path, find_csv_files, is_batch_input};
+use datalib::utils::util::{
+    default_output_path, file_exists, generate_report, get_file_name, is_std_stream,
+    resolve_config_path,
+};
+use std::path::{Path, PathBuf};
 
 #[tokio::test]
 async fn test_get_file_name() {
@@ -52,7 +33,7 @@ async fn test_get_file_name() {
 async fn test_generate_json_file() {
     const ZERO_PROB: f32 = 0.0;
     const ZERO: u32 = 0;
-    const JSON_FILE: &str = "test_generate_json_file";
+    const JSON_FILE: &str = "json/test_generate_json_file.json";
 
     let json_response = JsonOutput::new(
         vec![
@@ -61,12 +42,14 @@ async fn test_generate_json_file() {
                 String::from("Colonne1"),
                 ZERO,
                 ZERO_PROB,
+                ZERO_PROB,
             ),
             Anomaly::new(
                 String::from("Danger2"),
                 String::from("Colonne2"),
                 ZERO,
                 ZERO_PROB,
+                ZERO_PROB,
             ),
         ],
         String::from("test.csv"),
@@ -75,14 +58,16 @@ async fn test_generate_json_file() {
         100,
     );
 
-    generate_json_file(
+    generate_report(
         json_response.anomalies.clone(),
         0,
         0,
-        "test.csv",
-        JSON_FILE,
+        &CsvFile::new("test.csv", b','),
+        Path::new(JSON_FILE),
+        OutputFormat::Json,
         100,
-    );
+    )
+    .unwrap();
 
     assert!(std::path::Path::new("json/test_generate_json_file.json").exists());
 
@@ -92,6 +77,7 @@ async fn test_generate_json_file() {
     .unwrap();
 
     assert_eq!(content.analysed_file, "test.csv");
+    assert_eq!(content.encoding, "UTF-8");
     assert_eq!(content.anomalies.len(), 2);
 
     for (i, anomaly) in content.anomalies.iter().enumerate() {
@@ -99,11 +85,60 @@ async fn test_generate_json_file() {
         assert_eq!(anomaly.value, json_response.anomalies[i].value);
         assert_eq!(anomaly.score, json_response.anomalies[i].score);
         assert_eq!(anomaly.line, json_response.anomalies[i].line);
+        assert_eq!(anomaly.threshold, json_response.anomalies[i].threshold);
     }
 
     delete_file("json/test_generate_json_file.json");
 }
 
+#[tokio::test]
+async fn test_default_output_path() {
+    assert_eq!(
+        default_output_path("data/customers.csv", "json"),
+        PathBuf::from("json/customers.json")
+    );
+    assert_eq!(
+        default_output_path("C:\\Users\\test\\file.csv", "json"),
+        PathBuf::from("json/file.json")
+    );
+}
+
+#[tokio::test]
+async fn test_resolve_config_path() {
+    assert_eq!(
+        resolve_config_path(Some(Path::new("custom/config.json"))),
+        PathBuf::from("custom/config.json")
+    );
+    assert!(resolve_config_path(None).ends_with("config.json"));
+}
+
+#[tokio::test]
+async fn test_write_json_to_writer() {
+    let json_response: JsonOutput = JsonOutput::new(
+        vec![Anomaly::new(
+            String::from("<script>"),
+            String::from("Comment"),
+            2,
+            0.9,
+            0.8,
+        )],
+        String::from("-"),
+        1,
+        3,
+        10,
+    );
+
+    let mut buffer: Vec<u8> = Vec::new();
+    json_response.write_to(&mut buffer).unwrap();
+
+    let content: JsonOutput = serde_json::from_slice(&buffer).unwrap();
+    assert_eq!(content.analysed_file, "-");
+    assert_eq!(content.anomalies.len(), 1);
+    assert_eq!(content.anomalies[0].value, "<script>");
+    assert!(is_std_stream("-"));
+    assert!(!is_std_stream("file.csv"));
+}
+
 #[tokio::test]
 async fn tets_file_exists() {
     const FILE_NAME: &str = "test_file_exists.csv";
@@ -118,3 +153,239 @@ pub fn delete_file(file_name: &str) {
         eprintln!("Une erreur est survenue lors de la suppression du fichier: {e}");
     }
 }
+
+#[tokio::test]
+async fn test_batch_output_path() {
+    let output_dir: &Path = Path::new("reports");
+
+    assert_eq!(
+        batch_output_path(
+            Path::new("data/sub/file.csv"),
+            Path::new("data"),
+            output_dir,
+            "json"
+        ),
+        PathBuf::from("reports/sub/file.json")
+    );
+    assert_eq!(
+        batch_output_path(Path::new("file.CSV"), Path::new(""), output_dir, "json"),
+        PathBuf::from("reports/file.json")
+    );
+    assert_eq!(
+        batch_output_path(
+            Path::new("other/file.csv"),
+            Path::new("data"),
+            output_dir,
+            "json"
+        ),
+        PathBuf::from("reports/other/file.json")
+    );
+    assert_eq!(
+        batch_output_path(
+            Path::new("../other/file.csv"),
+            Path::new("data"),
+            output_dir,
+            "json"
+        ),
+        PathBuf::from("reports/__/other/file.json")
+    );
+    assert_eq!(
+        batch_output_path(
+            Path::new("/srv/exports/file.csv"),
+            Path::new("data"),
+            output_dir,
+            "json"
+        ),
+        PathBuf::from("reports/srv/exports/file.json")
+    );
+}
+
+#[tokio::test]
+async fn test_find_csv_files() {
+    const BATCH_DIR: &str = "test_find_csv_files";
+
+    std::fs::create_dir_all(format!("{BATCH_DIR}/sub")).unwrap();
+    for file in ["a.csv", "sub/b.CSV", "notes.txt"] {
+        std::fs::write(format!("{BATCH_DIR}/{file}"), "Name,Age\nJohn,25\n").unwrap();
+    }
+
+    assert!(is_batch_input(BATCH_DIR));
+    assert!(is_batch_input("data/*.csv"));
+    assert!(!is_batch_input("data/file.csv"));
+
+    let (base_dir, files): (PathBuf, Vec<PathBuf>) = find_csv_files(BATCH_DIR).unwrap();
+    assert_eq!(base_dir, PathBuf::from(BATCH_DIR));
+    assert_eq!(
+        files,
+        vec![
+            PathBuf::from(format!("{BATCH_DIR}/a.csv")),
+            PathBuf::from(format!("{BATCH_DIR}/sub/b.CSV"))
+        ]
+    );
+
+    let (base_dir, files): (PathBuf, Vec<PathBuf>) =
+        find_csv_files(&format!("{BATCH_DIR}/*.csv")).unwrap();
+    assert_eq!(base_dir, PathBuf::from(BATCH_DIR));
+    assert_eq!(files, vec![PathBuf::from(format!("{BATCH_DIR}/a.csv"))]);
+
+    let bracket_file: String = format!("{BATCH_DIR}/data[1].csv");
+    assert!(is_batch_input(&bracket_file));
+    std::fs::write(&bracket_file, "Name,Age\nJohn,25\n").unwrap();
+    assert!(!is_batch_input(&bracket_file));
+
+    std::fs::remove_dir_all(BATCH_DIR).unwrap();
+}
+
+#[tokio::test]
+async fn test_batch_summary() {
+    let summary: BatchSummary = BatchSummary::new(
+        vec![
+            FileSummary::new(
+                String::from("a.csv"),
+                String::from("json/a.json"),
+                2,
+                3,
+                10,
+                5,
+            ),
+            FileSummary::new(
+                String::from("b.csv"),
+                String::from("json/b.json"),
+                1,
+                1,
+                4,
+                5,
+            ),
+            FileSummary::failed(String::from("c.csv"), String::from("CSV error"), 1),
+        ],
+        12,
+    );
+
+    assert_eq!(summary.analysed_files, 2);
+    assert_eq!(summary.failed_files, 1);
+    assert_eq!(summary.anomalies, 3);
+    assert_eq!(summary.ai_analyze, 4);
+    assert_eq!(summary.regex_analyze, 14);
+}
+
+#[tokio::test]
+async fn test_generate_sarif_report() {
+    const SARIF_FILE: &str = "json/test_generate_sarif_report.sarif";
+
+    let mut anomaly: Anomaly = Anomaly::new(
+        String::from("' OR 'a'='a"),
+        String::from("Comment"),
+        3,
+        0.95,
+        0.8,
+    );
+    anomaly.column_index = 2;
+
+    generate_report(
+        vec![anomaly],
+        4,
+        1,
+        &CsvFile::new("data/test.csv", b','),
+        Path::new(SARIF_FILE),
+        OutputFormat::Sarif,
+        100,
+    )
+    .unwrap();
+
+    let content: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(SARIF_FILE).unwrap()).unwrap();
+
+    assert_eq!(content["version"], "2.1.0");
+    let run: &serde_json::Value = &content["runs"][0];
+    assert_eq!(run["tool"]["driver"]["name"], "DataLint");
+    assert_eq!(run["tool"]["driver"]["rules"][0]["id"], "model");
+    assert_eq!(run["properties"]["aiAnalyze"], 1);
+
+    let result: &serde_json::Value = &run["results"][0];
+    assert_eq!(result["ruleId"], "model");
+    assert_eq!(result["level"], "warning");
+    let location: &serde_json::Value = &result["locations"][0];
+    assert_eq!(
+        location["physicalLocation"]["artifactLocation"]["uri"],
+        "data/test.csv"
+    );
+    assert_eq!(location["physicalLocation"]["region"]["startLine"], 3);
+    assert_eq!(location["logicalLocations"][0]["name"], "Comment");
+    assert_eq!(location["logicalLocations"][0]["index"], 2);
+    assert_eq!(result["properties"]["source"], "model");
+    assert!((result["properties"]["score"].as_f64().unwrap() - 0.95).abs() < 1e-6);
+
+    delete_file(SARIF_FILE);
+}
+
+#[tokio::test]
+async fn test_generate_json_lines_report() {
+    const JSONL_FILE: &str = "json/test_generate_json_lines_report.jsonl";
+
+    generate_report(
+        vec![
+            Anomaly::new(String::from("Danger1"), String::from("Col1"), 2, 0.9, 0.8),
+            Anomaly::new(String::from("Danger2"), String::from("Col2"), 5, 0.95, 0.8),
+        ],
+        10,
+        2,
+        &CsvFile::new("test.csv", b','),
+        Path::new(JSONL_FILE),
+        OutputFormat::JsonLines,
+        100,
+    )
+    .unwrap();
+
+    let content: String = std::fs::read_to_string(JSONL_FILE).unwrap();
+    let records: Vec<serde_json::Value> = content
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    assert_eq!(records.len(), 3);
+    assert_eq!(records[0]["type"], "anomaly");
+    assert_eq!(records[0]["value"], "Danger1");
+    assert_eq!(records[1]["line"], 5);
+    assert_eq!(records[2]["type"], "summary");
+    assert_eq!(records[2]["analysed_file"], "test.csv");
+    assert_eq!(records[2]["anomalies"], 2);
+    assert_eq!(records[2]["regex_analyze"], 10);
+
+    delete_file(JSONL_FILE);
+}
+
+#[tokio::test]
+async fn test_generate_csv_report() {
+    const CSV_REPORT_FILE: &str = "json/test_generate_csv_report.csv";
+
+    let mut formula: Anomaly =
+        Anomaly::new(String::from("=1+1"), String::from("Col2"), 3, 0.9, 0.8);
+    formula.reasons = vec![
+        String::from("pattern:illegal_char_regex"),
+        String::from("model"),
+        String::from("token:="),
+    ];
+
+    generate_report(
+        vec![
+            Anomaly::new(String::from("a, b"), String::from("Col1"), 2, 0.5, 0.4),
+            formula,
+        ],
+        0,
+        2,
+        &CsvFile::new("test.csv", b','),
+        Path::new(CSV_REPORT_FILE),
+        OutputFormat::Csv,
+        100,
+    )
+    .unwrap();
+
+    let content: String = std::fs::read_to_string(CSV_REPORT_FILE).unwrap();
+    assert_eq!(
+        content,
+        "line,column,value,score,reason\n2,Col1,\"a, b\",0.5,model\n\
+         3,Col2,'=1+1,0.9,\"pattern:illegal_char_regex, model, token:=\"\n"
+    );
+
+    delete_file(CSV_REPORT_FILE);
+}
@@ -0,0 +1,9 @@
+use crate::structs::detection::Detection;
+
+/// Detects a category of unsafe values before the model.
+/// The values it reports are anomalies of their own category and severity, the model does not score them.
+pub trait Detector: Send + Sync {
+    /// Return the detection of the value, or `None` if the detector does not report it.
+    /// The value is given as read from the file, before its spaces are trimmed.
+    fn detect(&self, value: &str) -> Option<Detection>;
+}
@@ -0,0 +1,17 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::structs::anomaly::Anomaly;
+use crate::structs::report_summary::ReportSummary;
+use crate::structs::structure_finding::StructureFinding;
+
+/// Writes a report in a given format.
+/// Anomalies are given as soon as they are found, so that streaming formats write them without holding the whole report.
+pub trait ReportWriter {
+    /// Write an anomaly to the report.
+    fn write_anomaly(&mut self, anomaly: Anomaly) -> Result<(), DataLintError>;
+
+    /// Write a structural finding of the CSV file to the report, apart from the anomalies of its values.
+    fn write_finding(&mut self, finding: StructureFinding) -> Result<(), DataLintError>;
+
+    /// Write the summary of the analysis once every anomaly is written, and flush the report.
+    fn finish(&mut self, summary: &ReportSummary) -> Result<(), DataLintError>;
+}
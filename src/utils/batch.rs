@@ -0,0 +1,240 @@
+use crate::enums::datalint_error::DataLintError;
+use crate::enums::log_level::LogLevel;
+use crate::enums::output_format::OutputFormat;
+use crate::structs::batch_summary::FileSummary;
+use crate::structs::column_stats::ColumnStats;
+use crate::structs::csv_file::CsvFile;
+use crate::structs::loaded_model::LoadedModel;
+use crate::structs::logger::{log_and_print_message, log_message};
+use crate::structs::report_summary::ReportSummary;
+use crate::structs::structure_finding::StructureFinding;
+use crate::traits::report_writer::ReportWriter;
+use crate::utils::util::{create_report_writer, finish_report, is_same_file};
+use glob::{MatchOptions, Paths};
+use rayon::ThreadPool;
+use rayon::prelude::*;
+use std::ffi::OsStr;
+use std::path::{Component, Path, PathBuf};
+use std::time::Instant;
+
+/// Characters that make a path a glob pattern.
+const GLOB_CHARS: [char; 3] = ['*', '?', '['];
+
+/// Name replacing the parent directories `..` in the path of a report, which must stay in the output directory.
+const PARENT_DIR_NAME: &str = "__";
+
+/// Extension of the files scanned when a directory is given.
+const CSV_EXTENSION: &str = "csv";
+
+/// Options of the glob patterns, the extension of the CSV files being matched regardless of its case.
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Return true if the input designates several files: a directory or a glob pattern.
+/// An existing file whose name contains a glob character, such as `data[1].csv`, is not a pattern.
+#[inline]
+#[must_use]
+pub fn is_batch_input(input: &str) -> bool {
+    let path: &Path = Path::new(input);
+    path.is_dir() || (input.contains(GLOB_CHARS) && !path.exists())
+}
+
+/// Return the CSV files designated by a directory, scanned recursively, or by a glob pattern,
+/// with the directory the reports mirror the tree of.
+pub fn find_csv_files(input: &str) -> Result<(PathBuf, Vec<PathBuf>), DataLintError> {
+    let (base_dir, pattern): (PathBuf, String) = if Path::new(input).is_dir() {
+        let base_dir: PathBuf = PathBuf::from(input);
+        let pattern: String = base_dir
+            .join("**")
+            .join(format!("*.{CSV_EXTENSION}"))
+            .to_string_lossy()
+            .into_owned();
+        (base_dir, pattern)
+    } else {
+        (glob_base_dir(input), String::from(input))
+    };
+
+    let paths: Paths = glob::glob_with(&pattern, MATCH_OPTIONS)
+        .map_err(|e| DataLintError::Config(format!("Invalid glob pattern {pattern}: {e}")))?;
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    for path in paths {
+        let path: PathBuf = path.map_err(std::io::Error::from)?;
+        if path.is_file() {
+            files.push(path);
+        }
+    }
+    files.sort();
+
+    Ok((base_dir, files))
+}
+
+/// Return the leading components of a glob pattern that contain no glob character.
+#[must_use]
+fn glob_base_dir(pattern: &str) -> PathBuf {
+    Path::new(pattern)
+        .components()
+        .take_while(|component| !component.as_os_str().to_string_lossy().contains(GLOB_CHARS))
+        .collect()
+}
+
+/// Return the path of the report of a file, mirroring its path relative to the base directory in the output directory.
+/// A file outside the base directory keeps its whole path, its parent directories `..` becoming `__`,
+/// so that the reports of files with the same name in different directories do not overwrite each other.
+#[must_use]
+pub fn batch_output_path(
+    csv_file_path: &Path,
+    base_dir: &Path,
+    output_dir: &Path,
+    extension: &str,
+) -> PathBuf {
+    let relative_path: PathBuf = csv_file_path
+        .strip_prefix(base_dir)
+        .ok()
+        .filter(|path| {
+            path.components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        })
+        .map_or_else(
+            || {
+                csv_file_path
+                    .components()
+                    .filter_map(|component| match component {
+                        Component::Normal(name) => Some(name),
+                        Component::ParentDir => Some(OsStr::new(PARENT_DIR_NAME)),
+                        Component::Prefix(_) | Component::RootDir | Component::CurDir => None,
+                    })
+                    .collect()
+            },
+            Path::to_path_buf,
+        );
+
+    output_dir.join(relative_path).with_extension(extension)
+}
+
+/// Analyse the files in parallel with the same loaded model and write one report per file.
+/// The analysis uses `jobs` workers, or one per CPU when it is 0, and a failure on a file does not stop the others.
+pub fn scan_files(
+    loaded_model: &LoadedModel,
+    files: &[PathBuf],
+    base_dir: &Path,
+    output_dir: &Path,
+    format: OutputFormat,
+    separator: Option<u8>,
+    jobs: usize,
+) -> Result<Vec<FileSummary>, DataLintError> {
+    let pool: ThreadPool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| DataLintError::Config(format!("Error creating the worker pool: {e}")))?;
+
+    Ok(pool.install(|| {
+        files
+            .par_iter()
+            .map(|csv_file_path| {
+                let output_path: PathBuf =
+                    batch_output_path(csv_file_path, base_dir, output_dir, format.extension());
+                scan_file(loaded_model, csv_file_path, &output_path, format, separator)
+            })
+            .collect()
+    }))
+}
+
+/// Analyse a single file of a batch and write its report, returning its summary.
+fn scan_file(
+    loaded_model: &LoadedModel,
+    csv_file_path: &Path,
+    output_path: &Path,
+    format: OutputFormat,
+    separator: Option<u8>,
+) -> FileSummary {
+    let start_time: Instant = Instant::now();
+    let input_path: String = csv_file_path.to_string_lossy().replace('\\', "/");
+
+    let result: Result<(usize, u32, u32), DataLintError> = analyse_and_report(
+        loaded_model,
+        &input_path,
+        output_path,
+        format,
+        separator,
+        &start_time,
+    );
+
+    match result {
+        Ok((anomalies, ai_analyze, regex_analyze)) => {
+            log_message(
+                &format!(
+                    "Anomalies: [{anomalies}] in file : {input_path}, Execution time: [{:?}]",
+                    start_time.elapsed()
+                ),
+                &LogLevel::Info,
+            );
+            FileSummary::new(
+                input_path,
+                output_path.to_string_lossy().replace('\\', "/"),
+                anomalies,
+                ai_analyze,
+                regex_analyze,
+                start_time.elapsed().as_millis(),
+            )
+        }
+        Err(e) => {
+            log_and_print_message(
+                &format!("Error analysing file {input_path}: {e}"),
+                &LogLevel::Error,
+            );
+            FileSummary::failed(input_path, e.to_string(), start_time.elapsed().as_millis())
+        }
+    }
+}
+
+/// Analyse a file and write its report, returning the number of anomalies, AI analyses and regex analyses.
+fn analyse_and_report(
+    loaded_model: &LoadedModel,
+    input_path: &str,
+    output_path: &Path,
+    format: OutputFormat,
+    separator: Option<u8>,
+    start_time: &Instant,
+) -> Result<(usize, u32, u32), DataLintError> {
+    let csv_struct: CsvFile = CsvFile::from_file_with_separator(input_path, separator)?;
+    if is_same_file(Path::new(input_path), output_path) {
+        return Err(DataLintError::Config(format!(
+            "The report would overwrite the analysed file {input_path}"
+        )));
+    }
+
+    let mut report_writer: Box<dyn ReportWriter> = create_report_writer(output_path, format)?;
+    let mut anomalies: usize = 0;
+
+    let (ai_analyze, regex_analyze, column_stats, structure_findings): (
+        u32,
+        u32,
+        Vec<ColumnStats>,
+        Vec<StructureFinding>,
+    ) = loaded_model.analyse_streaming(&csv_struct, |anomaly| {
+        anomalies += 1;
+        report_writer.write_anomaly(anomaly)
+    })?;
+
+    let findings_count: usize = structure_findings.len();
+    for finding in structure_findings {
+        report_writer.write_finding(finding)?;
+    }
+
+    let summary: ReportSummary = ReportSummary::new(
+        &csv_struct,
+        anomalies,
+        findings_count,
+        ai_analyze,
+        regex_analyze,
+        start_time.elapsed().as_millis(),
+        column_stats,
+    );
+    finish_report(report_writer.as_mut(), &summary, output_path)?;
+
+    Ok((anomalies, ai_analyze, regex_analyze))
+}
@@ -0,0 +1,205 @@
+/// Number of decoding rounds, enough to undo a payload encoded up to three times.
+const MAX_DECODING_ROUNDS: usize = 3;
+
+/// Decode the HTML entities, the URL encoding and the JavaScript escapes of a value, as a browser would before
+/// running it. The decoding is repeated while it changes the value, so that nested encodings are undone too,
+/// and the NUL characters ignored by browsers are removed.
+#[must_use]
+pub fn decode_payload(value: &str) -> String {
+    let mut decoded: String = value.replace('\0', "");
+
+    for _ in 0..MAX_DECODING_ROUNDS {
+        let next: String = decode_js_escapes(&decode_url(&decode_html_entities(&decoded)));
+        if next == decoded {
+            break;
+        }
+        decoded = next;
+    }
+
+    decoded
+}
+
+/// Decode the named HTML entities used to hide a payload, such as `&lt;` or `&colon;`, and the numeric ones,
+/// such as `&#60;` or `&#x3C;`, whose semicolon browsers do not require.
+#[must_use]
+pub fn decode_html_entities(value: &str) -> String {
+    replace_escapes(value, '&', html_entity)
+}
+
+/// Decode the percent-encoded bytes of a URL, such as `%3C`, the `+` signs being kept as they are.
+#[must_use]
+pub fn decode_url(value: &str) -> String {
+    if !value.contains('%') {
+        return String::from(value);
+    }
+
+    let bytes: &[u8] = value.as_bytes();
+    let mut decoded: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut index: usize = 0;
+    while index < bytes.len() {
+        let encoded: Option<u8> = match bytes.get(index..index + 3) {
+            Some([b'%', high, low]) => hex_value(*high).zip(hex_value(*low)),
+            _ => None,
+        }
+        .map(|(high, low)| high << 4 | low);
+
+        if let Some(byte) = encoded {
+            decoded.push(byte);
+            index += 3;
+        } else {
+            decoded.push(bytes[index]);
+            index += 1;
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Decode the JavaScript escapes of characters, such as `\x3c`, `\u003c` or `\u{3c}`.
+#[must_use]
+pub fn decode_js_escapes(value: &str) -> String {
+    replace_escapes(value, '\\', js_escape)
+}
+
+/// Replace each escape starting with the marker by its character, keeping the marker when no escape starts there.
+fn replace_escapes(value: &str, marker: char, escape: fn(&str) -> Option<(char, usize)>) -> String {
+    let mut decoded: String = String::with_capacity(value.len());
+    let mut rest: &str = value;
+
+    while let Some(start) = rest.find(marker) {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let (character, length): (char, usize) =
+            escape(rest).unwrap_or((marker, marker.len_utf8()));
+        decoded.push(character);
+        rest = &rest[length..];
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// Return the character of the HTML entity at the start of the text, and the length of the entity.
+fn html_entity(text: &str) -> Option<(char, usize)> {
+    let body: &str = text.strip_prefix('&')?;
+
+    if let Some(number) = body.strip_prefix('#') {
+        let (digits, radix, prefix_length): (&str, u32, usize) =
+            match number.strip_prefix(['x', 'X']) {
+                Some(hex) => (hex, 16, 3),
+                None => (number, 10, 2),
+            };
+        let digits_length: usize = digits
+            .find(|character: char| !character.is_digit(radix))
+            .unwrap_or(digits.len());
+        let character: char = u32::from_str_radix(&digits[..digits_length], radix)
+            .ok()
+            .and_then(char::from_u32)?;
+        let semicolon: usize = usize::from(digits[digits_length..].starts_with(';'));
+        return Some((character, prefix_length + digits_length + semicolon));
+    }
+
+    let name_length: usize = body
+        .find(|character: char| !character.is_ascii_alphanumeric())
+        .unwrap_or(body.len());
+    let name: &str = &body[..name_length];
+    let has_semicolon: bool = body[name_length..].starts_with(';');
+
+    let character: char = match name {
+        "lt" | "LT" => '<',
+        "gt" | "GT" => '>',
+        "amp" | "AMP" => '&',
+        "quot" | "QUOT" => '"',
+        "apos" => '\'',
+        "colon" => ':',
+        "semi" => ';',
+        "sol" => '/',
+        "bsol" => '\\',
+        "lpar" => '(',
+        "rpar" => ')',
+        "equals" => '=',
+        "grave" => '`',
+        "excl" => '!',
+        "period" => '.',
+        "Tab" => '\t',
+        "NewLine" => '\n',
+        "nbsp" => ' ',
+        _ => return None,
+    };
+    // Only the oldest entities are decoded without their semicolon
+    if !has_semicolon && !matches!(character, '<' | '>' | '&' | '"') {
+        return None;
+    }
+
+    Some((character, 1 + name_length + usize::from(has_semicolon)))
+}
+
+/// Return the character of the JavaScript escape at the start of the text, and the length of the escape.
+fn js_escape(text: &str) -> Option<(char, usize)> {
+    let (digits, length): (&str, usize) = if let Some(hex) = text.strip_prefix("\\x") {
+        (hex.get(..2)?, 4)
+    } else if let Some(braced) = text.strip_prefix("\\u{") {
+        let end: usize = braced.find('}')?;
+        (&braced[..end], end + 4)
+    } else if let Some(hex) = text.strip_prefix("\\u") {
+        (hex.get(..4)?, 6)
+    } else {
+        return None;
+    };
+
+    if digits.is_empty() || !digits.bytes().all(|byte| byte.is_ascii_hexdigit()) {
+        return None;
+    }
+    let character: char = u32::from_str_radix(digits, 16)
+        .ok()
+        .and_then(char::from_u32)?;
+    Some((character, length))
+}
+
+/// Return the value of an ASCII hexadecimal digit.
+fn hex_value(byte: u8) -> Option<u8> {
+    char::from(byte)
+        .to_digit(16)
+        .and_then(|digit| u8::try_from(digit).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_decode_html_entities() {
+        assert_eq!(
+            decode_html_entities("&lt;script&gt;alert(&#39;x&#x27;)&lt;/script&gt;"),
+            "<script>alert('x')</script>"
+        );
+        assert_eq!(
+            decode_html_entities("javascript&colon;&#97&#108ert(1)"),
+            "javascript:alert(1)"
+        );
+        assert_eq!(
+            decode_html_entities("Tom & Jerry &copy; &#xZZ;"),
+            "Tom & Jerry &copy; &#xZZ;"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_url_and_js_escapes() {
+        assert_eq!(decode_url("%3Cscript%3E+%zz%4"), "<script>+%zz%4");
+        assert_eq!(
+            decode_js_escapes(r"\x3cimg src=x\u{3e}\n"),
+            "<img src=x>\\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_decode_payload() {
+        assert_eq!(
+            decode_payload("%26lt%3Bsvg%20onload%3Dalert(1)%26gt%3B"),
+            "<svg onload=alert(1)>"
+        );
+        assert_eq!(decode_payload("java\0script:"), "javascript:");
+        assert_eq!(decode_payload("Hélène 100%"), "Hélène 100%");
+    }
+}
@@ -0,0 +1,106 @@
+use chardetng::EncodingDetector;
+use encoding_rs::{Encoding, UTF_8, UTF_16BE, UTF_16LE};
+
+/// Detect the character encoding of a sample of bytes.
+/// A byte order mark is trusted first, then UTF-16 without BOM is recognised by its NUL bytes,
+/// valid UTF-8 is kept as is and any other sample is guessed among the single-byte code pages.
+#[must_use]
+pub fn detect_encoding(sample: &[u8]) -> &'static Encoding {
+    if let Some((encoding, _)) = Encoding::for_bom(sample) {
+        return encoding;
+    }
+
+    if let Some(encoding) = detect_utf16_without_bom(sample) {
+        return encoding;
+    }
+
+    // A multibyte character may be cut at the end of the sample
+    if std::str::from_utf8(sample).map_or_else(|e| e.error_len().is_none(), |_| true) {
+        return UTF_8;
+    }
+
+    let mut detector: EncodingDetector = EncodingDetector::new();
+    detector.feed(sample, true);
+    detector.guess(None, true)
+}
+
+/// Recognise UTF-16 text without BOM, where most ASCII characters have a NUL byte
+/// in the high half of each code unit.
+#[must_use]
+fn detect_utf16_without_bom(sample: &[u8]) -> Option<&'static Encoding> {
+    const MIN_NUL_RATIO: f32 = 0.3;
+    const MIN_CODE_UNITS: usize = 2;
+
+    let code_units: usize = sample.len() / 2;
+    if code_units < MIN_CODE_UNITS {
+        return None;
+    }
+
+    let (even_nuls, odd_nuls): (usize, usize) =
+        sample.chunks_exact(2).fold((0, 0), |(even, odd), unit| {
+            (
+                even + usize::from(unit[0] == 0),
+                odd + usize::from(unit[1] == 0),
+            )
+        });
+
+    let even_ratio: f32 = even_nuls as f32 / code_units as f32;
+    let odd_ratio: f32 = odd_nuls as f32 / code_units as f32;
+
+    if odd_ratio >= MIN_NUL_RATIO && even_nuls == 0 {
+        Some(UTF_16LE)
+    } else if even_ratio >= MIN_NUL_RATIO && odd_nuls == 0 {
+        Some(UTF_16BE)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use encoding_rs::WINDOWS_1252;
+
+    #[tokio::test]
+    async fn test_detect_utf8() {
+        assert_eq!(detect_encoding(b"Name,City\nJohn,Paris\n"), UTF_8);
+        assert_eq!(
+            detect_encoding("Nom,Ville\nHélène,Orléans\n".as_bytes()),
+            UTF_8
+        );
+        assert_eq!(detect_encoding(b""), UTF_8);
+
+        // The sample ends in the middle of "é"
+        let sample: &[u8] = "Hélène".as_bytes();
+        assert_eq!(detect_encoding(&sample[..2]), UTF_8);
+    }
+
+    #[tokio::test]
+    async fn test_detect_bom() {
+        assert_eq!(detect_encoding(b"\xEF\xBB\xBFName,City\n"), UTF_8);
+        assert_eq!(detect_encoding(b"\xFF\xFEN\0a\0m\0e\0"), UTF_16LE);
+        assert_eq!(detect_encoding(b"\xFE\xFF\0N\0a\0m\0e"), UTF_16BE);
+    }
+
+    #[tokio::test]
+    async fn test_detect_utf16_without_bom() {
+        let utf16le: Vec<u8> = "Nom;Ville\nHélène;Orléans\n"
+            .encode_utf16()
+            .flat_map(u16::to_le_bytes)
+            .collect();
+        let utf16be: Vec<u8> = "Nom;Ville\nHélène;Orléans\n"
+            .encode_utf16()
+            .flat_map(u16::to_be_bytes)
+            .collect();
+
+        assert_eq!(detect_encoding(&utf16le), UTF_16LE);
+        assert_eq!(detect_encoding(&utf16be), UTF_16BE);
+    }
+
+    #[tokio::test]
+    async fn test_detect_windows_1252() {
+        let (sample, _, _) = WINDOWS_1252
+            .encode("Nom;Prénom;Ville\nDurand;Hélène;Orléans\nMüller;François;Besançon\n");
+        assert_eq!(detect_encoding(&sample), WINDOWS_1252);
+    }
+}
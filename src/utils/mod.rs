@@ -1,2 +1,5 @@
+pub mod batch;
+pub mod decode;
+pub mod encoding;
 pub mod regex;
 pub mod util;
